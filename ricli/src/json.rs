@@ -0,0 +1,236 @@
+//! Non-interactive mode used with `--json`.
+//!
+//! Each line read from stdin describes a round as a JSON object like
+//! `{"game": 0, "target": "Red Circle", "robots": [[0, 1], [5, 4], [7, 1], [7, 15]]}`.
+//! `game` is the seed of a standard game as used by `quadrant::game_from_seed`, the robots are
+//! given in the order red, blue, green, yellow as zero based (column, row) pairs. For each line a
+//! JSON object containing the solution or an error is printed to stdout. Lines with robots on the
+//! same field or in the center block, or from which the target can't be reached, result in an
+//! error.
+
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead};
+
+use ricochet_board::{quadrant, Direction, PositionEncoding, Robot, RobotPositions, Round, Target};
+use ricochet_solver::util::LeastMovesBoard;
+use ricochet_solver::{IdaStar, Path, Solver};
+
+use crate::{parse_symbol, BOARD_SIZE};
+
+type Coordinate = (PositionEncoding, PositionEncoding);
+
+/// A round to solve read from stdin.
+#[derive(Debug, Deserialize)]
+struct RoundRequest {
+    game: usize,
+    target: String,
+    robots: [Coordinate; 4],
+}
+
+/// The solution of a round printed to stdout.
+#[derive(Debug, Serialize)]
+struct SolutionOutput {
+    start: Vec<Coordinate>,
+    end: Vec<Coordinate>,
+    /// The moves written as the first letter of the robot followed by the first letter of the
+    /// direction, e.g. `RU` for moving the red robot up.
+    moves: Vec<String>,
+    length: usize,
+    robots_used: usize,
+}
+
+/// Printed instead of a solution if a line could not be solved.
+#[derive(Debug, Serialize)]
+struct ErrorOutput {
+    error: String,
+}
+
+/// Solves the rounds read from stdin until it is closed.
+pub fn run() {
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line.expect("Failed to read from stdin");
+        if line.trim().is_empty() {
+            continue;
+        }
+        let output = match solve_line(&line) {
+            Ok(solution) => serde_json::to_string(&solution),
+            Err(error) => serde_json::to_string(&ErrorOutput { error }),
+        };
+        println!("{}", output.expect("Failed to serialize the output"));
+    }
+}
+
+/// Parses a round from `line` and solves it.
+fn solve_line(line: &str) -> Result<SolutionOutput, String> {
+    let request: RoundRequest = serde_json::from_str(line).map_err(|err| err.to_string())?;
+    if request
+        .robots
+        .iter()
+        .any(|&(col, row)| col >= BOARD_SIZE || row >= BOARD_SIZE)
+    {
+        return Err("A robot is outside of the board".to_string());
+    }
+    let center = BOARD_SIZE / 2 - 1..=BOARD_SIZE / 2;
+    if request
+        .robots
+        .iter()
+        .any(|(col, row)| center.contains(col) && center.contains(row))
+    {
+        return Err("A robot is in the center of the board".to_string());
+    }
+    for (i, robot) in request.robots.iter().enumerate() {
+        if request.robots[i + 1..].contains(robot) {
+            return Err("Two robots are on the same field".to_string());
+        }
+    }
+    let target = parse_target(&request.target)
+        .ok_or_else(|| format!("Invalid target: {}", request.target))?;
+
+    let game = quadrant::game_from_seed(request.game);
+    let target_position = game
+        .get_target_position(&target)
+        .ok_or("Failed to find the position of the target on the board")?;
+    let round = Round::new(game.board().clone(), target, target_position);
+    let start = RobotPositions::from_tuples(&request.robots);
+    if LeastMovesBoard::new(round.board(), target_position).is_unsolvable(&start, target) {
+        return Err("The target can't be reached from the robot positions".to_string());
+    }
+
+    let path = IdaStar::new().solve(&round, start);
+    Ok(SolutionOutput::from(&path))
+}
+
+/// Parses a target written as its color followed by its symbol, e.g. `Red Circle`, or `Spiral`.
+fn parse_target(input: &str) -> Option<Target> {
+    let input = input.to_lowercase();
+    let mut words = input.split_whitespace();
+    let color = words.next()?;
+    if let "spiral" | "s" = color {
+        return Some(Target::Spiral);
+    }
+    let symbol = parse_symbol(words.next()?)?;
+    match color {
+        "red" | "r" => Some(Target::Red(symbol)),
+        "blue" | "b" => Some(Target::Blue(symbol)),
+        "green" | "g" => Some(Target::Green(symbol)),
+        "yellow" | "y" => Some(Target::Yellow(symbol)),
+        _ => None,
+    }
+}
+
+impl From<&Path> for SolutionOutput {
+    fn from(path: &Path) -> Self {
+        let coordinates = |positions: &RobotPositions| -> Vec<Coordinate> {
+            positions.to_array().iter().map(|&pos| pos.into()).collect()
+        };
+        Self {
+            start: coordinates(path.start_pos()),
+            end: coordinates(path.end_pos()),
+            moves: path
+                .movements()
+                .iter()
+                .map(|&(robot, direction)| move_notation(robot, direction))
+                .collect(),
+            length: path.len(),
+            robots_used: path.robot_count(),
+        }
+    }
+}
+
+/// Writes a move as the first letter of the robot followed by the first letter of the direction.
+fn move_notation(robot: Robot, direction: Direction) -> String {
+    let robot = match robot {
+        Robot::Red => 'R',
+        Robot::Blue => 'B',
+        Robot::Green => 'G',
+        Robot::Yellow => 'Y',
+    };
+    let direction = match direction {
+        Direction::Up => 'U',
+        Direction::Right => 'R',
+        Direction::Down => 'D',
+        Direction::Left => 'L',
+    };
+    format!("{}{}", robot, direction)
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{Direction, Robot, RobotPositions, Symbol, Target};
+    use ricochet_solver::Path;
+
+    use super::{move_notation, parse_target, solve_line, SolutionOutput};
+
+    #[test]
+    fn targets() {
+        assert_eq!(
+            parse_target("Red Circle"),
+            Some(Target::Red(Symbol::Circle))
+        );
+        assert_eq!(parse_target("b h"), Some(Target::Blue(Symbol::Hexagon)));
+        assert_eq!(
+            parse_target(" yellow  triangle "),
+            Some(Target::Yellow(Symbol::Triangle))
+        );
+        assert_eq!(parse_target("Spiral"), Some(Target::Spiral));
+        assert_eq!(parse_target("Red"), None);
+        assert_eq!(parse_target("Purple Circle"), None);
+        assert_eq!(parse_target(""), None);
+    }
+
+    #[test]
+    fn notation() {
+        assert_eq!(move_notation(Robot::Red, Direction::Up), "RU");
+        assert_eq!(move_notation(Robot::Yellow, Direction::Left), "YL");
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        let end = RobotPositions::from_tuples(&[(0, 0), (5, 4), (7, 1), (15, 15)]);
+        let movements = vec![
+            (Robot::Red, Direction::Up),
+            (Robot::Yellow, Direction::Right),
+        ];
+        let output = SolutionOutput::from(&Path::new(start, end, movements));
+        assert_eq!(
+            serde_json::to_string(&output).unwrap(),
+            "{\"start\":[[0,1],[5,4],[7,1],[7,15]],\"end\":[[0,0],[5,4],[7,1],[15,15]],\
+             \"moves\":[\"RU\",\"YR\"],\"length\":2,\"robots_used\":2}"
+        );
+    }
+
+    /// Creates an input line for the standard game 0.
+    fn line(target: &str, robots: &str) -> String {
+        format!(
+            "{{\"game\": 0, \"target\": \"{}\", \"robots\": {}}}",
+            target, robots
+        )
+    }
+
+    #[test]
+    fn solved_line() {
+        let output = solve_line(&line("Spiral", "[[0, 1], [5, 4], [7, 1], [7, 15]]")).unwrap();
+        assert_eq!(output.start, vec![(0, 1), (5, 4), (7, 1), (7, 15)]);
+        assert_eq!(output.length, output.moves.len());
+    }
+
+    #[test]
+    fn error_lines() {
+        let error = |line: &str| solve_line(line).unwrap_err();
+        let robots = "[[0, 1], [5, 4], [7, 1], [7, 15]]";
+
+        assert!(error("not json").contains("expected"));
+        assert_eq!(error(&line("Red", robots)), "Invalid target: Red");
+        assert_eq!(
+            error(&line("Spiral", "[[0, 1], [5, 4], [7, 1], [16, 0]]")),
+            "A robot is outside of the board"
+        );
+        assert_eq!(
+            error(&line("Spiral", "[[0, 1], [5, 4], [8, 7], [7, 15]]")),
+            "A robot is in the center of the board"
+        );
+        assert_eq!(
+            error(&line("Spiral", "[[0, 1], [5, 4], [0, 1], [7, 15]]")),
+            "Two robots are on the same field"
+        );
+    }
+}
@@ -1,14 +1,22 @@
+mod json;
+
 use std::collections::HashSet;
 use text_io::{read, try_scan};
 
 use ricochet_board::{
-    quadrant, Game, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target,
+    draw_board_with_robots, quadrant, Game, PositionEncoding, Robot, RobotPositions, Round, Symbol,
+    Target,
 };
-use ricochet_solver::{IdaStar, Solver};
+use ricochet_solver::{IdaStar, Path, Solver};
 
 const BOARD_SIZE: PositionEncoding = quadrant::STANDARD_BOARD_SIZE;
 
 fn main() {
+    if std::env::args().skip(1).any(|arg| arg == "--json") {
+        json::run();
+        return;
+    }
+
     // Create the board
     let game = 'outer: loop {
         let game = build_board_from_parts();
@@ -26,6 +34,7 @@ fn main() {
 
     // Ask the user where the robots are positioned
     let mut positions = ask_for_robot_positions();
+    let mut history = PositionHistory::new();
 
     'game: loop {
         let target = ask_for_target();
@@ -33,8 +42,13 @@ fn main() {
             .get_target_position(&target)
             .expect("Failed to find the position of the target on the board");
         let round = Round::new(game.board().clone(), target, target_position);
+        println!(
+            "{}",
+            draw_board_with_robots(round.board().get_walls(), &positions, target_position)
+        );
 
         println!("Solving...");
+        history.push(positions.clone());
         let path = IdaStar::new().solve(&round, positions);
         let movements = path.movements();
         println!("Moves needed to reach target: {}", movements.len());
@@ -44,13 +58,31 @@ fn main() {
         for (move_n, (robot, dir)) in movements.iter().enumerate() {
             println!(" {:>2}  {:<8}{:<6}", move_n + 1, robot, dir);
         }
-        println!("Continue? (Y/n)");
+        if !movements.is_empty() {
+            println!("Step through the path? (y/N)");
+            loop {
+                let input: String = read!("{}\n");
+                match input.to_lowercase().trim() {
+                    "y" => {
+                        step_through(&round, &path);
+                        break;
+                    }
+                    "n" | "" => break,
+                    _ => println!("Input invalid! {}", input),
+                }
+            }
+        }
+        println!("Continue? (Y/n) Enter \"u\" to undo this round.");
 
         loop {
             let input: String = read!("{}\n");
             match input.to_lowercase().trim() {
                 "y" | "" => break,
                 "n" => break 'game,
+                "u" => {
+                    positions = undo_rounds(&mut history);
+                    continue 'game;
+                }
                 _ => println!("Input invalid! {}", input),
             }
         }
@@ -73,6 +105,74 @@ fn main() {
     }
 }
 
+/// Replays `path` one move at a time and draws the board after each move when enter is pressed.
+fn step_through(round: &Round, path: &Path) {
+    let positions = path.positions(round);
+    for (move_n, ((robot, dir), positions)) in
+        path.movements().iter().zip(&positions[1..]).enumerate()
+    {
+        println!("Press enter to show the next move.");
+        let _: String = read!("{}\n");
+        println!("Move {}: {} {}", move_n + 1, robot, dir);
+        println!(
+            "{}",
+            draw_board_with_robots(
+                round.board().get_walls(),
+                positions,
+                round.target_position()
+            )
+        );
+    }
+}
+
+/// Stores the robot positions at the start of each solved round.
+struct PositionHistory {
+    positions: Vec<RobotPositions>,
+}
+
+impl PositionHistory {
+    fn new() -> Self {
+        Self {
+            positions: Vec::new(),
+        }
+    }
+
+    /// Remembers `positions` as the start of a new round.
+    fn push(&mut self, positions: RobotPositions) {
+        self.positions.push(positions);
+    }
+
+    /// Removes the last round and returns the positions at its start.
+    fn pop(&mut self) -> Option<RobotPositions> {
+        self.positions.pop()
+    }
+
+    /// Checks if there are no rounds left to undo.
+    fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+/// Undoes the last round and keeps undoing earlier rounds as long as the user asks for it.
+///
+/// Returns the positions before the last undone round.
+fn undo_rounds(history: &mut PositionHistory) -> RobotPositions {
+    let mut positions = history
+        .pop()
+        .expect("Failed to undo a round without any history");
+    while !history.is_empty() {
+        println!("The robots are back at\n{}", positions);
+        println!("Undo the round before as well? (y/N)");
+        let input: String = read!("{}\n");
+        match input.to_lowercase().trim() {
+            "y" => positions = history.pop().unwrap(),
+            "n" | "" => break,
+            _ => println!("Input invalid! {}", input),
+        }
+    }
+    positions
+}
+
 fn ask_for_target() -> Target {
     let mut target;
     println!("What color is the target?");
@@ -124,16 +224,24 @@ fn ask_for_symbol() -> Symbol {
     println!("Accepted input: \"Circle\"(c), \"Triangle\"(t), \"Square\"(s), \"Hexagon\"(h)");
     loop {
         let shape: String = read!("{}\n");
-        match shape.to_lowercase().trim() {
-            "circle" | "c" => return Symbol::Circle,
-            "triangle" | "t" => return Symbol::Triangle,
-            "square" | "s" => return Symbol::Square,
-            "hexagon" | "h" => return Symbol::Hexagon,
-            _ => println!("Input invalid: {}", shape),
+        match parse_symbol(&shape) {
+            Some(symbol) => return symbol,
+            None => println!("Input invalid: {}", shape),
         }
     }
 }
 
+/// Parses the name of a symbol or its first letter.
+fn parse_symbol(input: &str) -> Option<Symbol> {
+    match input.to_lowercase().trim() {
+        "circle" | "c" => Some(Symbol::Circle),
+        "triangle" | "t" => Some(Symbol::Triangle),
+        "square" | "s" => Some(Symbol::Square),
+        "hexagon" | "h" => Some(Symbol::Hexagon),
+        _ => None,
+    }
+}
+
 fn ask_for_robot_positions() -> RobotPositions {
     let mut positions = [(0, 0); 4];
     'outer: loop {
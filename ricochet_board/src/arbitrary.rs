@@ -0,0 +1,129 @@
+//! [`proptest`](proptest) strategies to generate random boards, robot positions and targets.
+//!
+//! The [`Arbitrary`](proptest::arbitrary::Arbitrary) implementations generate values for the
+//! standard board size. Use [`arb_board`](arb_board) and
+//! [`arb_robot_positions`](arb_robot_positions) for other sizes.
+
+use itertools::Itertools;
+use proptest::prelude::*;
+use proptest::sample;
+
+use crate::generator::{Generator, CENTER_WALLS_FROM_SIDE_LENGTH};
+use crate::quadrant::STANDARD_BOARD_SIZE;
+use crate::{Board, Position, PositionEncoding, RobotPositions, Target, TARGETS};
+
+/// Creates a strategy generating enclosed boards with the given `side_length`.
+///
+/// The boards are created by a [`Generator`](Generator) with a random seed, so they contain a
+/// center block if `side_length` is at least
+/// [`CENTER_WALLS_FROM_SIDE_LENGTH`](CENTER_WALLS_FROM_SIDE_LENGTH).
+pub fn arb_board(side_length: PositionEncoding) -> impl Strategy<Value = Board> {
+    any::<u128>().prop_map(move |seed| Generator::from_seed(seed, side_length).generate_board())
+}
+
+/// Creates a strategy generating positions of four robots on distinct fields of a board with the
+/// given `side_length`.
+///
+/// The fields in the center block are never chosen on boards with a center block.
+///
+/// # Panics
+/// Panics if `side_length` is less than 2, since there are fewer than four fields.
+pub fn arb_robot_positions(side_length: PositionEncoding) -> impl Strategy<Value = RobotPositions> {
+    assert!(
+        side_length >= 2,
+        "Four robots need a board with a side length of at least 2"
+    );
+    let center = side_length / 2 - 1..=side_length / 2;
+    let has_center = side_length >= CENTER_WALLS_FROM_SIDE_LENGTH;
+    let fields = (0..side_length)
+        .cartesian_product(0..side_length)
+        .filter(|(c, r)| !(has_center && center.contains(c) && center.contains(r)))
+        .collect::<Vec<_>>();
+
+    sample::subsequence(fields, 4)
+        .prop_shuffle()
+        .prop_map(|fields| {
+            RobotPositions::from_tuples(&[fields[0], fields[1], fields[2], fields[3]])
+        })
+}
+
+impl Arbitrary for Board {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        arb_board(STANDARD_BOARD_SIZE).boxed()
+    }
+}
+
+impl Arbitrary for RobotPositions {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        arb_robot_positions(STANDARD_BOARD_SIZE).boxed()
+    }
+}
+
+impl Arbitrary for Target {
+    type Parameters = ();
+    type Strategy = sample::Select<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        sample::select(TARGETS.to_vec())
+    }
+}
+
+impl Arbitrary for Position {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (0..STANDARD_BOARD_SIZE, 0..STANDARD_BOARD_SIZE)
+            .prop_map(Position::from)
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    use super::{arb_board, arb_robot_positions};
+    use crate::{Board, RobotPositions};
+
+    proptest! {
+        #[test]
+        fn boards_are_enclosed(board in arb_board(9)) {
+            let last = board.side_length() as usize - 1;
+            let walls = board.get_walls();
+            prop_assert!(walls.iter().all(|col| col[last].down));
+            prop_assert!(walls[last].iter().all(|field| field.right));
+        }
+
+        #[test]
+        fn robots_on_distinct_fields(positions in any::<RobotPositions>()) {
+            let fields = positions.to_array();
+            prop_assert!(fields.iter().all_unique());
+            for pos in fields.iter() {
+                prop_assert!(!((7..=8).contains(&pos.column()) && (7..=8).contains(&pos.row())));
+            }
+        }
+
+        #[test]
+        fn robots_stay_on_distinct_fields(positions in arb_robot_positions(10)) {
+            let board = Board::new_empty(10).wall_enclosure().set_center_walls().unwrap();
+            for (moved, _) in positions.reachable_positions(&board) {
+                prop_assert!(moved.to_array().iter().all_unique());
+                prop_assert_eq!(moved.differing_robots(&positions).len(), 1);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "side length of at least 2")]
+    fn robot_positions_too_small() {
+        let _ = arb_robot_positions(1);
+    }
+}
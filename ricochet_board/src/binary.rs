@@ -0,0 +1,173 @@
+//! Reading and writing boards in a compact binary format.
+//!
+//! All numbers are stored as little-endian `u32`. The format consists of
+//! 1. the side length `n` of the board,
+//! 2. a bitboard marking all fields with a wall to the right,
+//! 3. a bitboard marking all fields with a wall at the bottom,
+//! 4. the number of obstacles followed by the column and row of each obstacle.
+//!
+//! A bitboard contains one bit per field, going through the fields row by row. Field `column,row`
+//! is stored in bit `i % 8` of byte `i / 8` with `i = row * n + column`, where bit 0 is the least
+//! significant bit. Unused bits in the last byte are zero, so a bitboard takes `ceil(n² / 8)`
+//! bytes.
+
+use std::convert::{TryFrom, TryInto};
+
+use crate::{Board, Position, PositionEncoding};
+
+impl Board {
+    /// Encodes the board in the binary format described in the [module docs](self).
+    ///
+    /// # Panics
+    /// Panics if the side length of the board doesn't fit into a `u32`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let side_length = self.side_length();
+        let fields = side_length as usize * side_length as usize;
+        let bitboard_len = fields.div_ceil(8);
+        let mut bytes = Vec::with_capacity(8 + 2 * bitboard_len + 8 * self.obstacles().len());
+
+        bytes.extend_from_slice(&to_u32(side_length).to_le_bytes());
+        let mut right = vec![0; bitboard_len];
+        let mut down = vec![0; bitboard_len];
+        for row in 0..side_length {
+            for column in 0..side_length {
+                let i = row as usize * side_length as usize + column as usize;
+                let field = self[Position::new(column, row)];
+                right[i / 8] |= (field.right as u8) << (i % 8);
+                down[i / 8] |= (field.down as u8) << (i % 8);
+            }
+        }
+        bytes.extend(right);
+        bytes.extend(down);
+
+        bytes.extend_from_slice(&(self.obstacles().len() as u32).to_le_bytes());
+        for obstacle in self.obstacles() {
+            bytes.extend_from_slice(&to_u32(obstacle.column()).to_le_bytes());
+            bytes.extend_from_slice(&to_u32(obstacle.row()).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a board written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader { bytes };
+
+        let side_length = reader.read_u32()?;
+        if side_length as usize > PositionEncoding::MAX as usize {
+            return Err("The side length is too large for this build");
+        }
+        let side_length = side_length as PositionEncoding;
+        if side_length == 0 {
+            return Err("The side length has to be at least 1");
+        }
+        let fields = side_length as usize * side_length as usize;
+        let bitboard_len = fields.div_ceil(8);
+        let right = reader.take(bitboard_len)?;
+        let down = reader.take(bitboard_len)?;
+
+        let mut board = Board::new_empty(side_length);
+        for row in 0..side_length {
+            for column in 0..side_length {
+                let i = row as usize * side_length as usize + column as usize;
+                let field = &mut board[Position::new(column, row)];
+                field.right = right[i / 8] & (1 << (i % 8)) != 0;
+                field.down = down[i / 8] & (1 << (i % 8)) != 0;
+            }
+        }
+
+        let obstacle_count = reader.read_u32()?;
+        let mut obstacles = Vec::new();
+        for _ in 0..obstacle_count {
+            let column = reader.read_u32()?;
+            let row = reader.read_u32()?;
+            if column >= side_length as u32 || row >= side_length as u32 {
+                return Err("An obstacle is outside of the board");
+            }
+            obstacles.push(Position::new(
+                column as PositionEncoding,
+                row as PositionEncoding,
+            ));
+        }
+        if !reader.bytes.is_empty() {
+            return Err("There are bytes left after the board");
+        }
+
+        Ok(board.set_obstacles(obstacles))
+    }
+}
+
+/// Converts a coordinate into a `u32`.
+///
+/// # Panics
+/// Panics if the coordinate doesn't fit into a `u32`.
+// Depending on the features, `PositionEncoding` is a `u16`, `u32` or `u64`.
+#[allow(clippy::unnecessary_fallible_conversions, clippy::useless_conversion)]
+fn to_u32(value: PositionEncoding) -> u32 {
+    u32::try_from(value).expect("Coordinates have to fit into a u32 to be encoded")
+}
+
+/// Reads values from the front of a byte slice.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Removes the first `len` bytes and returns them.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        if self.bytes.len() < len {
+            return Err("The bytes end before the board is complete");
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    /// Reads a little-endian `u32`.
+    fn read_u32(&mut self) -> Result<u32, &'static str> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{quadrant, Board, Position};
+
+    #[test]
+    fn layout() {
+        let board = Board::new_empty(3)
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(2, 2, 1)
+            .set_obstacles(vec![Position::new(1, 2)]);
+        assert_eq!(
+            board.to_bytes(),
+            vec![3, 0, 0, 0, 1, 0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let board = quadrant::game_from_seed(0).board().clone();
+        let bytes = board.to_bytes();
+        assert_eq!(bytes.len(), 4 + 2 * 32 + 4);
+        assert_eq!(Board::from_bytes(&bytes), Ok(board));
+
+        let with_obstacles =
+            Board::new_empty(5).set_obstacles(vec![Position::new(4, 0), Position::new(2, 3)]);
+        assert_eq!(
+            Board::from_bytes(&with_obstacles.to_bytes()),
+            Ok(with_obstacles)
+        );
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let bytes = quadrant::game_from_seed(0).board().to_bytes();
+        assert!(Board::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Board::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+        assert!(Board::from_bytes(&[0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+        assert!(
+            Board::from_bytes(&[1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]).is_err()
+        );
+    }
+}
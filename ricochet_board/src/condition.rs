@@ -0,0 +1,85 @@
+use std::convert::TryInto;
+use std::ops;
+
+use crate::{Position, RobotPositions, Target, ROBOTS};
+
+/// Decides when a [`Round`](crate::Round) is solved.
+///
+/// Rounds use the [`StandardCondition`](StandardCondition) unless a different condition is set
+/// with [`Round::with_condition`](crate::Round::with_condition). Implementing this trait allows
+/// variants of the rules with other winning conditions without changing the solvers.
+pub trait TargetCondition: Send + Sync {
+    /// Checks if the robots on `positions` fulfill the condition.
+    fn is_satisfied(&self, positions: &RobotPositions) -> bool;
+
+    /// Returns a lower bound of the number of moves needed to fulfill the condition starting from
+    /// `positions`.
+    ///
+    /// `least_moves` contains the minimum number of moves a single robot needs to reach the target
+    /// position of the round from each field. The bound must never be greater than the actual
+    /// number of moves, otherwise optimal solvers may return longer solutions. Returns 0 by
+    /// default, which is always correct but doesn't help the solvers.
+    fn lower_bound(
+        &self,
+        least_moves: &dyn ops::Index<Position, Output = usize>,
+        positions: &RobotPositions,
+    ) -> usize {
+        let _ = (least_moves, positions);
+        0
+    }
+}
+
+/// The condition of the standard rules, which is fulfilled if the robot of the target's color is
+/// on the target position, or any robot in case of the spiral target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StandardCondition {
+    target: Target,
+    target_position: Position,
+}
+
+impl StandardCondition {
+    /// Creates the condition to reach `target` on `target_position`.
+    pub fn new(target: Target, target_position: Position) -> Self {
+        Self {
+            target,
+            target_position,
+        }
+    }
+
+    /// Returns the minimum in `least_moves` of the robots allowed to reach the target.
+    pub(crate) fn least_moves<I>(&self, least_moves: &I, positions: &RobotPositions) -> usize
+    where
+        I: ops::Index<Position, Output = usize> + ?Sized,
+    {
+        match self.target.try_into() {
+            Ok(robot) => least_moves[positions[robot]],
+            Err(_) => ROBOTS
+                .iter()
+                .map(|&robot| least_moves[positions[robot]])
+                .min()
+                .expect("Failed to find minimum number of moves to the target."),
+        }
+    }
+}
+
+impl TargetCondition for StandardCondition {
+    fn is_satisfied(&self, positions: &RobotPositions) -> bool {
+        match self.target {
+            Target::Spiral => positions.contains_any_robot(self.target_position),
+            _ => positions.contains_colored_robot(
+                self.target
+                    .try_into()
+                    .expect("Failed to extract the robot corresponding to the target"),
+                self.target_position,
+            ),
+        }
+    }
+
+    fn lower_bound(
+        &self,
+        least_moves: &dyn ops::Index<Position, Output = usize>,
+        positions: &RobotPositions,
+    ) -> usize {
+        self.least_moves(least_moves, positions)
+    }
+}
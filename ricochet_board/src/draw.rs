@@ -1,4 +1,4 @@
-use crate::Field;
+use crate::{ColorTheme, Field, Position, RobotPositions, ROBOTS};
 use draw_a_box::{find_character, Weight};
 
 /// Width per field in the string in number of characters.
@@ -22,6 +22,61 @@ pub fn draw_board(walls: &[Vec<Field>]) -> String {
     output
 }
 
+/// Creates a string representation of the walls of a board with the robots and the target.
+///
+/// Robots are marked with the first letter of their color and the target with `*`. A robot on the
+/// target is marked with its letter followed by `*`.
+pub fn draw_board_with_robots(
+    walls: &[Vec<Field>],
+    positions: &RobotPositions,
+    target_position: Position,
+) -> String {
+    draw_board_with_theme(walls, positions, target_position, &ColorTheme::default())
+}
+
+/// Creates a string representation of the walls of a board with the robots and the target.
+///
+/// Like [`draw_board_with_robots`](draw_board_with_robots), but the robots are marked with their
+/// glyphs in `theme`.
+pub fn draw_board_with_theme(
+    walls: &[Vec<Field>],
+    positions: &RobotPositions,
+    target_position: Position,
+    theme: &ColorTheme,
+) -> String {
+    let glyphs: Vec<String> = ROBOTS
+        .iter()
+        .map(|&robot| theme.glyph(robot).to_string())
+        .collect();
+    let (mut canvas, _) = create_board_string_vec(walls);
+    let field_center = |pos: Position| {
+        (
+            pos.column() as usize * FIELD_DRAW_WIDTH + FIELD_DRAW_WIDTH / 2,
+            pos.row() as usize * FIELD_DRAW_HEIGHT + FIELD_DRAW_HEIGHT / 2,
+        )
+    };
+
+    let (target_col, target_row) = field_center(target_position);
+    canvas[target_col][target_row] = "*";
+    for (&robot, glyph) in ROBOTS.iter().zip(&glyphs) {
+        let (col, row) = field_center(positions[robot]);
+        if positions[robot] == target_position {
+            canvas[col + 1][row] = "*";
+        }
+        canvas[col][row] = glyph;
+    }
+
+    let mut output = String::new();
+    for row in 0..canvas[0].len() {
+        for col in &canvas {
+            output.push_str(col[row]);
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
 /// Creates the strings making up the board and used by `draw_board` to create the actual
 /// visualization.
 ///
@@ -107,3 +162,43 @@ pub fn create_board_string_vec(walls: &[Vec<Field>]) -> (Vec<Vec<&str>>, Vec<Vec
 
     (canvas, corner_weights)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{draw_board_with_robots, draw_board_with_theme};
+    use crate::{Board, ColorTheme, Position, Robot, RobotPositions};
+
+    #[test]
+    fn robots_and_target() {
+        let board = Board::new_empty(3).wall_enclosure();
+        let positions = RobotPositions::from_tuples(&[(0, 0), (1, 0), (2, 0), (0, 2)]);
+        let char_at = |drawn: &str, col: usize, row: usize| {
+            drawn.lines().nth(row).unwrap().chars().nth(col).unwrap()
+        };
+
+        let drawn = draw_board_with_robots(board.get_walls(), &positions, Position::new(2, 2));
+        assert_eq!(char_at(&drawn, 2, 1), 'R');
+        assert_eq!(char_at(&drawn, 7, 1), 'B');
+        assert_eq!(char_at(&drawn, 12, 1), 'G');
+        assert_eq!(char_at(&drawn, 2, 5), 'Y');
+        assert_eq!(char_at(&drawn, 3, 5), ' ');
+        assert_eq!(char_at(&drawn, 12, 5), '*');
+
+        let drawn = draw_board_with_robots(board.get_walls(), &positions, Position::new(0, 2));
+        assert_eq!(char_at(&drawn, 2, 5), 'Y');
+        assert_eq!(char_at(&drawn, 3, 5), '*');
+    }
+
+    #[test]
+    fn themed_glyphs() {
+        let board = Board::new_empty(3).wall_enclosure();
+        let positions = RobotPositions::from_tuples(&[(0, 0), (1, 0), (2, 0), (0, 2)]);
+        let theme = ColorTheme::default().set_robot(Robot::Red, "Rot", '1');
+
+        let drawn =
+            draw_board_with_theme(board.get_walls(), &positions, Position::new(2, 2), &theme);
+        let second_row = drawn.lines().nth(1).unwrap();
+        assert_eq!(second_row.chars().nth(2), Some('1'));
+        assert_eq!(second_row.chars().nth(7), Some('B'));
+    }
+}
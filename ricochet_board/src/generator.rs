@@ -10,6 +10,10 @@ use rand::{Rng, SeedableRng};
 /// Marks the side_length from which on generated boards contain a center wall block.
 pub const CENTER_WALLS_FROM_SIDE_LENGTH: PositionEncoding = 10;
 
+/// The number of boards [`random_standard_like`](Board::random_standard_like) generates before
+/// giving up.
+pub const RANDOM_BOARD_ATTEMPTS: usize = 100;
+
 /// A board generator to create boards of different sizes and configurations.
 #[derive(Debug)]
 pub struct Generator {
@@ -74,7 +78,9 @@ impl Generator {
         self.occupied_fields = BTreeSet::new();
 
         if self.side_length >= CENTER_WALLS_FROM_SIDE_LENGTH {
-            base = base.set_center_walls();
+            base = base
+                .set_center_walls()
+                .expect("Boards with center walls are large enough to contain them");
             let f = self.side_length / 2 - 1;
             for (col_add, row_add) in [0, 1].iter().cartesian_product(&[0, 1]) {
                 self.add_occupied_field(Position::new(f + col_add, f + row_add));
@@ -252,9 +258,36 @@ impl Generator {
     }
 }
 
+impl Board {
+    /// Generates a random board resembling the standard board with `seed`.
+    ///
+    /// Like the standard board, it is enclosed by walls, has walls protruding from the outer walls
+    /// and fields with two walls forming a corner, which can be used as targets. Boards with a
+    /// `side_length` of at least [`CENTER_WALLS_FROM_SIDE_LENGTH`](CENTER_WALLS_FROM_SIDE_LENGTH)
+    /// also have an enclosed block in the center. Boards on which a corner can't be reached by a
+    /// robot starting anywhere else are discarded and generated again. Returns `None` if all of the
+    /// first [`RANDOM_BOARD_ATTEMPTS`](RANDOM_BOARD_ATTEMPTS) boards have been discarded.
+    ///
+    /// # Panics
+    /// Panics if `side_length` is less than `5`, since smaller boards lack room for the walls and
+    /// targets.
+    pub fn random_standard_like(seed: u64, side_length: PositionEncoding) -> Option<Self> {
+        assert!(
+            side_length >= 5,
+            "Random boards need a side length of at least 5"
+        );
+        let mut generator = Generator::from_seed(seed.into(), side_length);
+        (0..RANDOM_BOARD_ATTEMPTS)
+            .map(|_| generator.generate_game())
+            .find(|game| !game.has_unreachable_target())
+            .map(|game| game.board)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Generator;
+    use crate::Board;
 
     #[test]
     fn different_seeds() {
@@ -270,4 +303,30 @@ mod tests {
             gen.generate_game();
         }
     }
+
+    #[test]
+    fn random_standard_like() {
+        for seed in 0..20 {
+            let board = Board::random_standard_like(seed, 16).unwrap();
+            assert_eq!(board.side_length(), 16);
+            assert_eq!(Some(board), Board::random_standard_like(seed, 16));
+        }
+        assert_ne!(
+            Board::random_standard_like(0, 16),
+            Board::random_standard_like(1, 16)
+        );
+
+        // Small boards return after a limited number of attempts, even without a valid board.
+        for side_length in 5..10 {
+            if let Some(board) = Board::random_standard_like(0, side_length) {
+                assert_eq!(board.side_length(), side_length);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn random_standard_like_too_small() {
+        Board::random_standard_like(0, 4);
+    }
 }
@@ -22,18 +22,32 @@
 //! needed. The crate provides these parts to make board creation easier, see the
 //! [`quadrant`](quadrant) module for more information.
 
+#[cfg(any(test, feature = "test-support"))]
+pub mod arbitrary;
+mod binary;
+mod condition;
 mod draw;
 pub mod generator;
+mod move_table;
+mod notation;
 mod positions;
 pub mod quadrant;
-
-use std::collections::BTreeMap;
-use std::convert::{TryFrom, TryInto};
+mod rotation;
+#[cfg(feature = "serde")]
+mod serialization;
+mod theme;
+
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryFrom;
+use std::sync::Arc;
 use std::{fmt, ops};
 
-pub use crate::draw::draw_board;
-pub use crate::positions::{Position, PositionEncoding, RobotPositions};
+pub use crate::condition::{StandardCondition, TargetCondition};
+pub use crate::draw::{draw_board, draw_board_with_robots, draw_board_with_theme};
+pub use crate::move_table::MoveTable;
+pub use crate::positions::{MoveResult, Position, PositionEncoding, RobotPositions};
 use crate::quadrant::{BoardQuadrant, Orientation, WallDirection};
+pub use crate::theme::ColorTheme;
 
 /// The type used to store the walls on a board.
 pub type Walls = Vec<Vec<Field>>;
@@ -52,12 +66,7 @@ pub const ROBOTS: [Robot; 4] = [Robot::Red, Robot::Blue, Robot::Green, Robot::Ye
 /// All targets in the game.
 pub const TARGETS: [Target; 17] = {
     let mut targets = [Target::Spiral; 17];
-    let symbols = &[
-        Symbol::Circle,
-        Symbol::Triangle,
-        Symbol::Square,
-        Symbol::Hexagon,
-    ];
+    let symbols = Symbol::all();
     let mut target_idx = 1;
     let mut symbol_idx = 0;
     while symbol_idx < 4 {
@@ -73,7 +82,11 @@ pub const TARGETS: [Target; 17] = {
 
 /// A field on the board.
 ///
-/// Contains information regarding walls to the right and bottom of the field.
+/// Contains information regarding walls to the right and bottom of the field. A wall between two
+/// fields is therefore always stored on the field with the lower column or row, walls to the left
+/// or top of a field are stored on its neighbor. The walls to the left of the first column and
+/// above the first row are stored on the last column and row respectively, since movement wraps
+/// around the edges of the board.
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
 pub struct Field {
     /// Returns `true` if the wall in the down direction is set.
@@ -92,22 +105,49 @@ pub struct Game {
 /// One round of a ricochet game.
 ///
 /// Represents the problem of finding a path from a starting position on a board to a given target.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct Round {
     board: Board,
     target: Target,
     target_position: Position,
+    /// Fields robots are not allowed to stop on.
+    forbidden_stops: HashSet<Position>,
+    forbidden_stop_rule: ForbiddenStopRule,
+    /// Replaces the [`StandardCondition`](StandardCondition) if set.
+    condition: Option<Arc<dyn TargetCondition>>,
+}
+
+/// Decides what happens to a move which would end on a field robots are not allowed to stop on.
+///
+/// Robots may always pass over these fields, only the end of a move is affected.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForbiddenStopRule {
+    /// The move can't be made.
+    Disallowed,
+    /// The robot passes through the forbidden fields at the end of the move and stops on the
+    /// last field before them it may stop on. The move can't be made if there is no such field.
+    PassThrough,
 }
 
 /// A ricochet robots board containing walls, but no targets.
+///
+/// A board may also contain obstacles, which block the movement of robots like other robots do,
+/// but are never moved.
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Board {
     walls: Walls,
+    obstacles: Vec<Position>,
 }
 
 /// The robots identified by their color.
+///
+/// This is the only type used to refer to the color of a robot, e.g. in
+/// [`RobotPositions`](RobotPositions) or the color of a [`Target`](Target). The color of a board
+/// quadrant can be converted from and into a `Robot`.
 #[allow(missing_docs)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Robot {
     Red,
     Blue,
@@ -132,6 +172,7 @@ pub enum Target {
 
 /// Symbols used with colored targets to differentiate between targets of the same color.
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Symbol {
     Circle,
@@ -142,6 +183,7 @@ pub enum Symbol {
 
 /// The directions a robot can be moved in.
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Direction {
     Up,
@@ -150,6 +192,43 @@ pub enum Direction {
     Left,
 }
 
+impl Direction {
+    /// Returns the direction pointing the other way, e.g. `Down` for `Up`.
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+/// The corners of a field, each formed by the walls in two directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    /// The corner formed by the walls above and to the left of a field.
+    UpperLeft,
+    /// The corner formed by the walls above and to the right of a field.
+    UpperRight,
+    /// The corner formed by the walls below and to the right of a field.
+    BottomRight,
+    /// The corner formed by the walls below and to the left of a field.
+    BottomLeft,
+}
+
+impl Corner {
+    /// Returns the directions of the two walls forming the corner.
+    pub fn directions(self) -> [Direction; 2] {
+        match self {
+            Corner::UpperLeft => [Direction::Up, Direction::Left],
+            Corner::UpperRight => [Direction::Up, Direction::Right],
+            Corner::BottomRight => [Direction::Down, Direction::Right],
+            Corner::BottomLeft => [Direction::Down, Direction::Left],
+        }
+    }
+}
+
 impl fmt::Display for Direction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let string = format!("{:?}", &self);
@@ -160,16 +239,57 @@ impl fmt::Display for Direction {
 impl fmt::Display for Target {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let string = match *self {
-            Target::Red(symb) => format!("Red {:?}", symb),
-            Target::Blue(symb) => format!("Blue {:?}", symb),
-            Target::Green(symb) => format!("Green {:?}", symb),
-            Target::Yellow(symb) => format!("Yellow {:?}", symb),
+            Target::Red(symb) => format!("{} {:?}", Robot::Red, symb),
+            Target::Blue(symb) => format!("{} {:?}", Robot::Blue, symb),
+            Target::Green(symb) => format!("{} {:?}", Robot::Green, symb),
+            Target::Yellow(symb) => format!("{} {:?}", Robot::Yellow, symb),
             Target::Spiral => "Spiral".to_string(),
         };
         f.pad(&string)
     }
 }
 
+impl Target {
+    /// Returns the index of the target's color in the order red, blue, green, yellow and the
+    /// spiral, which can be reached by any robot.
+    pub fn color_index(self) -> usize {
+        match self {
+            Target::Red(_) => 0,
+            Target::Blue(_) => 1,
+            Target::Green(_) => 2,
+            Target::Yellow(_) => 3,
+            Target::Spiral => 4,
+        }
+    }
+}
+
+impl Symbol {
+    /// Returns all symbols in the order `Circle`, `Triangle`, `Square`, and `Hexagon`.
+    pub const fn all() -> [Symbol; 4] {
+        [
+            Symbol::Circle,
+            Symbol::Triangle,
+            Symbol::Square,
+            Symbol::Hexagon,
+        ]
+    }
+
+    /// Returns the index of the symbol in [`Symbol::all`].
+    pub fn to_index(self) -> usize {
+        match self {
+            Symbol::Circle => 0,
+            Symbol::Triangle => 1,
+            Symbol::Square => 2,
+            Symbol::Hexagon => 3,
+        }
+    }
+
+    /// Returns the symbol at `index` in [`Symbol::all`] or `None` if `index` is greater than 3.
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::all().get(index).copied()
+    }
+}
+
 impl TryFrom<Target> for Robot {
     type Error = &'static str;
 
@@ -185,9 +305,9 @@ impl TryFrom<Target> for Robot {
 }
 
 impl fmt::Display for Robot {
+    /// Writes the name of the robot in the default [`ColorTheme`](ColorTheme).
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let string = format!("{:?}", &self);
-        f.pad(&string)
+        f.pad(theme::DEFAULT_NAMES[theme::robot_index(*self)])
     }
 }
 
@@ -204,13 +324,47 @@ impl Board {
             panic!("Tried to create a non-square board.")
         }
 
-        Self { walls }
+        Self {
+            walls,
+            obstacles: Vec::new(),
+        }
     }
 
     /// Create a new empty board with no walls with `side_length`.
     pub fn new_empty(side_length: PositionEncoding) -> Self {
         Self {
             walls: vec![vec![Field::default(); side_length as usize]; side_length as usize],
+            obstacles: Vec::new(),
+        }
+    }
+
+    /// Creates a board with `side_length` by calling `f` with the column and row of every field to
+    /// get its walls.
+    ///
+    /// The enclosing walls are stored on the last column and row like on every other board, so
+    /// they have to be returned by `f` or added with [`wall_enclosure`](Board::wall_enclosure).
+    ///
+    /// ```
+    /// use ricochet_board::{Board, Field, Position};
+    ///
+    /// // Every second column has a wall to its right.
+    /// let board = Board::from_fn(8, |column, _| Field {
+    ///     down: false,
+    ///     right: column % 2 == 1,
+    /// })
+    /// .wall_enclosure();
+    /// assert!(board[Position::new(3, 5)].right);
+    /// assert!(!board[Position::new(4, 5)].right);
+    /// ```
+    pub fn from_fn<F>(side_length: PositionEncoding, mut f: F) -> Self
+    where
+        F: FnMut(PositionEncoding, PositionEncoding) -> Field,
+    {
+        Self {
+            walls: (0..side_length)
+                .map(|column| (0..side_length).map(|row| f(column, row)).collect())
+                .collect(),
+            obstacles: Vec::new(),
         }
     }
 
@@ -220,15 +374,29 @@ impl Board {
     }
 
     /// Encloses the board with walls.
+    ///
+    /// Returns the board unchanged if it is already enclosed.
     pub fn wall_enclosure(self) -> Self {
+        if self.is_enclosed() {
+            return self;
+        }
         let side_length = self.side_length();
         self.enclose_lengths(0, 0, side_length, side_length)
     }
 
     /// Creates a 2x2 block enclosed by walls in the center of the board.
-    pub fn set_center_walls(self) -> Self {
+    ///
+    /// Setting the walls again doesn't change the board. On boards with an odd side length the
+    /// block is placed above and to the left of the center field.
+    ///
+    /// # Errors
+    /// Returns an error if the side length of the board is less than 2.
+    pub fn set_center_walls(self) -> Result<Self, &'static str> {
+        if self.side_length() < 2 {
+            return Err("The board is too small to contain a center block");
+        }
         let point = self.side_length() / 2 - 1;
-        self.enclose_lengths(point, point, 2, 2)
+        Ok(self.enclose_lengths(point, point, 2, 2))
     }
 
     /// Encloses a rectangle defined by the left upper corner and its width and height.
@@ -265,6 +433,22 @@ impl Board {
             .set_vertical_line(right_col, row, len)
     }
 
+    /// Places immovable obstacles on the `obstacles` positions, replacing any previous ones.
+    ///
+    /// # Panics
+    /// Panics if any of the positions is out of bounds.
+    pub fn set_obstacles(mut self, obstacles: Vec<Position>) -> Self {
+        let side_length = self.side_length();
+        if obstacles
+            .iter()
+            .any(|pos| pos.column() >= side_length || pos.row() >= side_length)
+        {
+            panic!("Tried to place an obstacle outside of the board.")
+        }
+        self.obstacles = obstacles;
+        self
+    }
+
     /// Starting from `[col, row]` sets `len` fields downwards to have a wall on the right side.
     #[inline]
     pub fn set_vertical_line(
@@ -306,6 +490,63 @@ impl Board {
         &mut self.walls
     }
 
+    /// Checks if all fields at the edge of the board are separated by walls from the fields on the
+    /// opposite edge.
+    ///
+    /// Movement wraps around at the edges like [`Position::to_direction`](Position::to_direction),
+    /// so robots on a board which isn't enclosed may leave it on one edge and continue on the
+    /// opposite one. Boards built by hand should be checked with this before solving rounds on
+    /// them.
+    pub fn is_enclosed(&self) -> bool {
+        let last = match self.walls.len().checked_sub(1) {
+            Some(last) => last,
+            None => return true,
+        };
+        self.walls[last].iter().all(|field| field.right)
+            && self.walls.iter().all(|col| col[last].down)
+    }
+
+    /// Lists the walls which differ between `self` and `other`.
+    ///
+    /// Each entry contains the field, the side of the field and whether `self` has a wall there.
+    /// The fields are compared column by column, obstacles aren't compared.
+    ///
+    /// # Errors
+    /// Returns an error if the boards have different side lengths.
+    pub fn diff(
+        &self,
+        other: &Board,
+    ) -> Result<Vec<(Position, WallDirection, bool)>, &'static str> {
+        if self.side_length() != other.side_length() {
+            return Err("The boards have different side lengths");
+        }
+
+        let mut differences = Vec::new();
+        for (column, (fields, other_fields)) in self.walls.iter().zip(&other.walls).enumerate() {
+            for (row, (field, other_field)) in fields.iter().zip(other_fields).enumerate() {
+                let pos = Position::new(column as PositionEncoding, row as PositionEncoding);
+                if field.right != other_field.right {
+                    differences.push((pos, WallDirection::Right, field.right));
+                }
+                if field.down != other_field.down {
+                    differences.push((pos, WallDirection::Down, field.down));
+                }
+            }
+        }
+        Ok(differences)
+    }
+
+    /// Returns the positions of the obstacles on the board.
+    pub fn obstacles(&self) -> &[Position] {
+        &self.obstacles
+    }
+
+    /// Checks if there is an obstacle on `pos`.
+    #[inline(always)]
+    pub fn is_obstacle(&self, pos: Position) -> bool {
+        !self.obstacles.is_empty() && self.obstacles.contains(&pos)
+    }
+
     /// Checks if a wall is next to `pos` in the given `direction`.
     pub fn is_adjacent_to_wall(&self, pos: Position, direction: Direction) -> bool {
         match direction {
@@ -321,6 +562,118 @@ impl Board {
             }
         }
     }
+
+    /// Checks if a robot on `pos` can move at least one field in `direction`, i.e. there is no
+    /// wall in between and the adjacent field isn't `occupied` or an obstacle.
+    ///
+    /// Unlike [`RobotPositions`](RobotPositions), `occupied` may contain any number of robots.
+    pub fn can_move(&self, pos: Position, direction: Direction, occupied: &[Position]) -> bool {
+        if self.is_adjacent_to_wall(pos, direction) {
+            return false;
+        }
+        let next = pos.to_direction(direction, self.side_length());
+        !occupied.contains(&next) && !self.is_obstacle(next)
+    }
+
+    /// Slides a robot from `pos` in `direction` until it is stopped by a wall, an obstacle or one
+    /// of the robots on `others`.
+    ///
+    /// Returns the field the robot stops on and the number of fields it traveled, which is 0 if it
+    /// can't move at all. `others` may contain the sliding robot itself on `pos`.
+    pub fn slide(
+        &self,
+        pos: Position,
+        direction: Direction,
+        others: &RobotPositions,
+    ) -> (Position, usize) {
+        let mut stop = pos;
+        let mut traveled = 0;
+        while others.adjacent_reachable(self, stop, direction) {
+            stop = stop.to_direction(direction, self.side_length());
+            traveled += 1;
+        }
+        (stop, traveled)
+    }
+
+    /// Sets a wall next to `pos` in the given `direction`.
+    ///
+    /// Like in [`is_adjacent_to_wall`](Board::is_adjacent_to_wall), walls above or to the left of
+    /// `pos` are stored on its neighbor.
+    fn add_wall(&mut self, pos: Position, direction: Direction) {
+        let side_length = self.side_length();
+        let field = match direction {
+            Direction::Right | Direction::Down => pos,
+            Direction::Left | Direction::Up => pos.to_direction(direction, side_length),
+        };
+        let field = &mut self.walls[field.column() as usize][field.row() as usize];
+        match direction {
+            Direction::Right | Direction::Left => field.right = true,
+            Direction::Down | Direction::Up => field.down = true,
+        }
+    }
+
+    /// Returns all positions a single robot starting at `from` can stop on, if there are no other
+    /// robots on the board. Obstacles on the board are taken into account.
+    ///
+    /// The returned set always contains `from`.
+    pub fn stoppable_cells(&self, from: Position) -> HashSet<Position> {
+        let mut stoppable = HashSet::new();
+        stoppable.insert(from);
+        let mut to_expand = vec![from];
+
+        while let Some(pos) = to_expand.pop() {
+            for &dir in DIRECTIONS.iter() {
+                let mut stop = pos;
+                while !self.is_adjacent_to_wall(stop, dir)
+                    && !self.is_obstacle(stop.to_direction(dir, self.side_length()))
+                {
+                    stop = stop.to_direction(dir, self.side_length());
+                }
+                if stoppable.insert(stop) {
+                    to_expand.push(stop);
+                }
+            }
+        }
+
+        stoppable
+    }
+
+    /// Partitions all fields without an obstacle into regions of fields which are mutually
+    /// reachable by a single robot, if there are no other robots on the board.
+    ///
+    /// The regions are ordered by their first field, going through the fields column by column.
+    pub fn reachable_regions(&self) -> Vec<HashSet<Position>> {
+        Self::regions_from_reachable(&self.reachable_fields())
+    }
+
+    /// Returns the [`stoppable_cells`](Board::stoppable_cells) of every field without an obstacle.
+    fn reachable_fields(&self) -> BTreeMap<Position, HashSet<Position>> {
+        let side_length = self.side_length();
+        (0..side_length)
+            .flat_map(|col| (0..side_length).map(move |row| Position::new(col, row)))
+            .filter(|&pos| !self.is_obstacle(pos))
+            .map(|pos| (pos, self.stoppable_cells(pos)))
+            .collect()
+    }
+
+    /// Partitions the fields in `reachable` into regions of mutually reachable fields.
+    fn regions_from_reachable(
+        reachable: &BTreeMap<Position, HashSet<Position>>,
+    ) -> Vec<HashSet<Position>> {
+        let mut regions: Vec<HashSet<Position>> = Vec::new();
+        for (pos, reachable_from_pos) in reachable {
+            if regions.iter().any(|region| region.contains(pos)) {
+                continue;
+            }
+            let region = reachable_from_pos
+                .iter()
+                .filter(|other| reachable[other].contains(pos))
+                .copied()
+                .collect();
+            regions.push(region);
+        }
+        regions
+    }
 }
 
 impl ops::Index<Position> for Board {
@@ -344,9 +697,106 @@ impl Round {
             board,
             target,
             target_position,
+            forbidden_stops: HashSet::new(),
+            forbidden_stop_rule: ForbiddenStopRule::Disallowed,
+            condition: None,
+        }
+    }
+
+    /// Replaces the [`StandardCondition`](StandardCondition) deciding when the round is solved with
+    /// `condition`.
+    ///
+    /// The [`target`](Round::target) and [`target_position`](Round::target_position) stay
+    /// unchanged, solvers still use them to calculate the minimum number of moves to the target
+    /// position passed to [`TargetCondition::lower_bound`](TargetCondition::lower_bound).
+    pub fn with_condition(mut self, condition: Arc<dyn TargetCondition>) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Returns the custom condition set with [`with_condition`](Round::with_condition).
+    pub fn condition(&self) -> Option<&Arc<dyn TargetCondition>> {
+        self.condition.as_ref()
+    }
+
+    /// Forbids robots to stop on the fields in `stops`, handling moves ending on them according to
+    /// `rule`.
+    ///
+    /// This is a variant of the standard rules, e.g. to forbid stopping on targets of other colors.
+    /// Robots moved with [`move_robot`](Round::move_robot) or
+    /// [`reachable_positions`](Round::reachable_positions) never stop on these fields, moving them
+    /// on the [`Board`](Board) directly ignores them.
+    pub fn with_forbidden_stops(
+        mut self,
+        stops: HashSet<Position>,
+        rule: ForbiddenStopRule,
+    ) -> Self {
+        self.forbidden_stops = stops;
+        self.forbidden_stop_rule = rule;
+        self
+    }
+
+    /// Returns the fields robots are not allowed to stop on.
+    pub fn forbidden_stops(&self) -> &HashSet<Position> {
+        &self.forbidden_stops
+    }
+
+    /// Returns how moves ending on a forbidden field are handled.
+    pub fn forbidden_stop_rule(&self) -> ForbiddenStopRule {
+        self.forbidden_stop_rule
+    }
+
+    /// Moves `robot` in `direction` and returns the new positions, taking the
+    /// [`forbidden_stops`](Round::forbidden_stops) into account.
+    ///
+    /// Returns `None` if the move doesn't change the positions or isn't allowed.
+    pub fn move_robot(
+        &self,
+        positions: &RobotPositions,
+        robot: Robot,
+        direction: Direction,
+    ) -> Option<RobotPositions> {
+        let mut moved = positions
+            .clone()
+            .try_move_in_direction(&self.board, robot, direction)
+            .ok()?;
+        if self.forbidden_stops.is_empty() || !self.forbidden_stops.contains(&moved[robot]) {
+            return Some(moved);
+        }
+
+        match self.forbidden_stop_rule {
+            ForbiddenStopRule::Disallowed => None,
+            ForbiddenStopRule::PassThrough => {
+                // Go back the way the robot came until it is on a field it may stop on.
+                let start = positions[robot];
+                let mut stop = moved[robot];
+                while self.forbidden_stops.contains(&stop) {
+                    stop = stop.to_direction(direction.opposite(), self.board.side_length());
+                    if stop == start {
+                        return None;
+                    }
+                }
+                moved.set_robot(robot, stop);
+                Some(moved)
+            }
         }
     }
 
+    /// Creates an Iterator over all positions reachable in one move that differ from `positions`,
+    /// taking the [`forbidden_stops`](Round::forbidden_stops) into account.
+    pub fn reachable_positions<'a>(
+        &'a self,
+        positions: &'a RobotPositions,
+    ) -> impl Iterator<Item = (RobotPositions, (Robot, Direction))> + 'a {
+        ROBOTS
+            .iter()
+            .flat_map(|&robot| DIRECTIONS.iter().map(move |&direction| (robot, direction)))
+            .filter_map(move |(robot, direction)| {
+                self.move_robot(positions, robot, direction)
+                    .map(|moved| (moved, (robot, direction)))
+            })
+    }
+
     /// Returns the `Board` the robots move on.
     pub fn board(&self) -> &Board {
         &self.board
@@ -362,20 +812,62 @@ impl Round {
         self.target_position
     }
 
-    /// Checks if the target has been reached.
+    /// Returns the [`color_index`](Target::color_index) of the target.
+    pub fn target_color_index(&self) -> usize {
+        self.target.color_index()
+    }
+
+    /// Checks if the target has been reached, or the custom [`condition`](Round::condition) is
+    /// fulfilled if there is one.
     pub fn target_reached(&self, positions: &RobotPositions) -> bool {
-        match self.target {
-            Target::Spiral => positions.contains_any_robot(self.target_position),
-            _ => positions.contains_colored_robot(
-                self.target
-                    .try_into()
-                    .expect("Failed to extract the robot corresponding to the target"),
-                self.target_position,
-            ),
+        match &self.condition {
+            Some(condition) => condition.is_satisfied(positions),
+            None => self.standard_condition().is_satisfied(positions),
+        }
+    }
+
+    /// Returns a lower bound of the number of moves needed to solve the round from `positions`.
+    ///
+    /// `least_moves` contains the minimum number of moves a single robot needs to reach the target
+    /// position from each field. See
+    /// [`TargetCondition::lower_bound`](TargetCondition::lower_bound).
+    pub fn lower_bound<I>(&self, least_moves: &I, positions: &RobotPositions) -> usize
+    where
+        I: ops::Index<Position, Output = usize>,
+    {
+        match &self.condition {
+            Some(condition) => condition.lower_bound(least_moves, positions),
+            None => self
+                .standard_condition()
+                .least_moves(least_moves, positions),
         }
     }
+
+    /// Returns the condition of the standard rules for the target of the round.
+    fn standard_condition(&self) -> StandardCondition {
+        StandardCondition::new(self.target, self.target_position)
+    }
 }
 
+impl PartialEq for Round {
+    /// Rounds with custom conditions are only equal if they share the same condition.
+    fn eq(&self, other: &Self) -> bool {
+        let same_condition = match (&self.condition, &other.condition) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        self.board == other.board
+            && self.target == other.target
+            && self.target_position == other.target_position
+            && self.forbidden_stops == other.forbidden_stops
+            && self.forbidden_stop_rule == other.forbidden_stop_rule
+            && same_condition
+    }
+}
+
+impl Eq for Round {}
+
 impl Game {
     /// Creates a new game with the given board and targets.
     pub fn new(board: Board, targets: BTreeMap<Target, Position>) -> Self {
@@ -393,10 +885,14 @@ impl Game {
     }
 
     /// Creates a new game with an enclosed board with a enclosed 2x2 block in the center.
+    ///
+    /// # Panics
+    /// Panics if `side_length` is less than 2.
     pub fn new_enclosed(side_length: PositionEncoding) -> Self {
         let board = Board::new_empty(side_length)
             .wall_enclosure() // Set outer walls
-            .set_center_walls(); // Set walls around the four center fields
+            .set_center_walls() // Set walls around the four center fields
+            .expect("Tried to create an enclosed game with a side length less than 2");
 
         Game {
             board,
@@ -418,6 +914,124 @@ impl Game {
     pub fn get_target_position(&self, target: &Target) -> Option<Position> {
         self.targets.get(target).cloned()
     }
+
+    /// Returns the target on `pos` if there is one.
+    ///
+    /// This is a linear scan over all targets, which is cheap for the 17 targets of a standard
+    /// game. If multiple targets are on `pos`, the first one in the order given by their `Ord`
+    /// implementation is returned.
+    pub fn target_at(&self, pos: Position) -> Option<Target> {
+        self.iter_targets()
+            .find(|&(_, target_pos)| target_pos == pos)
+            .map(|(target, _)| target)
+    }
+
+    /// Creates an iterator over all targets and their positions.
+    ///
+    /// The targets are yielded in the order given by their `Ord` implementation.
+    pub fn iter_targets(&self) -> impl Iterator<Item = (Target, Position)> + '_ {
+        self.targets.iter().map(|(&target, &pos)| (target, pos))
+    }
+
+    /// Returns the number of targets on the board.
+    pub fn target_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Checks if `target` is on the board.
+    pub fn has_target(&self, target: &Target) -> bool {
+        self.targets.contains_key(target)
+    }
+
+    /// Checks if the spiral target is on the board.
+    pub fn has_spiral(&self) -> bool {
+        self.has_target(&Target::Spiral)
+    }
+
+    /// Checks if any target lies in one of the [`reachable_regions`](Board::reachable_regions)
+    /// of the board which can't be entered by a single robot starting outside of it.
+    pub fn has_unreachable_target(&self) -> bool {
+        let reachable = self.board.reachable_fields();
+        let regions = Board::regions_from_reachable(&reachable);
+        self.targets.values().any(|target_pos| {
+            let region = match regions.iter().find(|region| region.contains(target_pos)) {
+                Some(region) => region,
+                None => return true,
+            };
+            reachable
+                .iter()
+                .filter(|(pos, _)| !region.contains(pos))
+                .all(|(_, reachable_from_pos)| !reachable_from_pos.contains(target_pos))
+        })
+    }
+
+    /// Adds the walls, obstacles and targets of `other` to the game.
+    ///
+    /// A wall is set if it is set in either game. Targets contained in both games have to be on the
+    /// same field.
+    ///
+    /// # Errors
+    /// Returns an error and leaves the game unchanged if the boards differ in size, a target is on
+    /// different fields in both games or two different targets are on the same field.
+    pub fn merge(&mut self, other: &Game) -> Result<(), &'static str> {
+        if self.board.side_length() != other.board.side_length() {
+            return Err("the boards have different sizes");
+        }
+        for (target, pos) in other.iter_targets() {
+            match self.targets.get(&target) {
+                Some(&own_pos) if own_pos != pos => {
+                    return Err("a target is on different fields in both games")
+                }
+                None if self.target_at(pos).is_some() => {
+                    return Err("two different targets are on the same field")
+                }
+                _ => {}
+            }
+        }
+
+        for (own, other) in self.board.walls.iter_mut().zip(&other.board.walls) {
+            for (own_field, other_field) in own.iter_mut().zip(other) {
+                own_field.right |= other_field.right;
+                own_field.down |= other_field.down;
+            }
+        }
+        for &obstacle in other.board.obstacles() {
+            if !self.board.is_obstacle(obstacle) {
+                self.board.obstacles.push(obstacle);
+            }
+        }
+        self.targets.extend(other.iter_targets());
+        Ok(())
+    }
+
+    /// Places `target` on `pos` and sets the walls forming `corner` around it.
+    ///
+    /// Targets on the physical board are always placed in a corner, so a robot can stop on them.
+    /// If `target` is already on the board, it is moved to `pos`.
+    pub fn add_target_with_corner(&mut self, target: Target, pos: Position, corner: Corner) {
+        for &direction in corner.directions().iter() {
+            self.board.add_wall(pos, direction);
+        }
+        self.targets.insert(target, pos);
+    }
+
+    /// Returns a robot which sits on a target it can reach in a round and that target.
+    ///
+    /// Like [`Round::target_reached`](Round::target_reached), a robot has to be of the same color
+    /// as the target, but any robot can reach the spiral. The targets are checked in the order
+    /// given by their `Ord` implementation.
+    pub fn robot_on_target(&self, positions: &RobotPositions) -> Option<(Robot, Target)> {
+        self.iter_targets().find_map(|(target, pos)| {
+            let robot = match Robot::try_from(target) {
+                Ok(robot) => Some(robot).filter(|&robot| positions[robot] == pos),
+                Err(_) => ROBOTS
+                    .iter()
+                    .copied()
+                    .find(|&robot| positions[robot] == pos),
+            };
+            robot.map(|robot| (robot, target))
+        })
+    }
 }
 
 impl Game {
@@ -483,7 +1097,13 @@ impl fmt::Debug for Game {
 
 #[cfg(test)]
 mod tests {
-    use crate::{quadrant, Board, Direction, Game, Position, Robot, RobotPositions};
+    use crate::{
+        quadrant, Board, Corner, Direction, Field, ForbiddenStopRule, Game, Position,
+        PositionEncoding, Robot, RobotPositions, Round, Symbol, Target, TargetCondition,
+        WallDirection, DIRECTIONS,
+    };
+    use std::collections::{BTreeMap, HashSet};
+    use std::sync::Arc;
 
     fn create_board() -> (RobotPositions, Board) {
         let quadrants = quadrant::gen_quadrants()
@@ -507,6 +1127,249 @@ mod tests {
         create_board();
     }
 
+    #[test]
+    fn board_diff() {
+        let board = Board::new_empty(4).set_vertical_line(1, 0, 2);
+        let other = Board::new_empty(4)
+            .set_vertical_line(1, 1, 1)
+            .set_horizontal_line(2, 3, 1);
+        assert_eq!(
+            board.diff(&other),
+            Ok(vec![
+                (Position::new(1, 0), WallDirection::Right, true),
+                (Position::new(2, 3), WallDirection::Down, false),
+            ])
+        );
+        assert_eq!(board.diff(&board), Ok(Vec::new()));
+        assert!(board.diff(&Board::new_empty(5)).is_err());
+    }
+
+    #[test]
+    fn board_from_fn() {
+        let (_, board) = create_board();
+        let copy = Board::from_fn(board.side_length(), |column, row| {
+            board[Position::new(column, row)]
+        });
+        assert_eq!(copy, board);
+
+        let striped = Board::from_fn(4, |_, row| Field {
+            down: row == 1,
+            right: false,
+        });
+        assert_eq!(striped, Board::new_empty(4).set_horizontal_line(0, 1, 4));
+    }
+
+    #[test]
+    fn forbidden_stops() {
+        let board = Board::new_empty(8).wall_enclosure();
+        let target = Target::Red(Symbol::Circle);
+        let pos = RobotPositions::from_tuples(&[(0, 0), (7, 7), (5, 0), (0, 6)]);
+        let forbidden: HashSet<_> = vec![Position::new(4, 0), Position::new(3, 0)]
+            .into_iter()
+            .collect();
+
+        let round = Round::new(board.clone(), target, Position::new(1, 1));
+        let moved = round
+            .move_robot(&pos, Robot::Red, Direction::Right)
+            .unwrap();
+        assert_eq!(moved[Robot::Red], Position::new(4, 0));
+
+        let disallowed = round
+            .clone()
+            .with_forbidden_stops(forbidden.clone(), ForbiddenStopRule::Disallowed);
+        assert_eq!(
+            disallowed.move_robot(&pos, Robot::Red, Direction::Right),
+            None
+        );
+        assert_eq!(
+            disallowed.move_robot(&pos, Robot::Red, Direction::Down),
+            round.move_robot(&pos, Robot::Red, Direction::Down)
+        );
+        assert_eq!(
+            disallowed.reachable_positions(&pos).count(),
+            round.reachable_positions(&pos).count() - 1
+        );
+
+        let pass_through = round.with_forbidden_stops(forbidden, ForbiddenStopRule::PassThrough);
+        let moved = pass_through
+            .move_robot(&pos, Robot::Red, Direction::Right)
+            .unwrap();
+        assert_eq!(moved[Robot::Red], Position::new(2, 0));
+        assert_eq!(
+            pass_through.move_robot(&moved, Robot::Red, Direction::Right),
+            None
+        );
+    }
+
+    #[test]
+    fn custom_condition() {
+        struct BothInRow(PositionEncoding);
+        impl TargetCondition for BothInRow {
+            fn is_satisfied(&self, positions: &RobotPositions) -> bool {
+                positions[Robot::Red].row() == self.0 && positions[Robot::Blue].row() == self.0
+            }
+        }
+
+        let target = Target::Red(Symbol::Circle);
+        let round = Round::new(
+            Board::new_empty(8).wall_enclosure(),
+            target,
+            Position::new(1, 0),
+        );
+        let pos = RobotPositions::from_tuples(&[(1, 0), (7, 0), (5, 2), (0, 6)]);
+        assert!(round.target_reached(&pos));
+
+        let custom = round.clone().with_condition(Arc::new(BothInRow(7)));
+        assert!(!custom.target_reached(&pos));
+        let moved = pos.move_in_direction(custom.board(), Robot::Blue, Direction::Down);
+        assert!(!custom.target_reached(&moved));
+        let moved = moved.move_in_direction(custom.board(), Robot::Red, Direction::Down);
+        assert!(custom.target_reached(&moved));
+
+        assert_ne!(custom, round);
+        assert_eq!(custom.clone(), custom);
+        assert_ne!(custom, round.with_condition(Arc::new(BothInRow(7))));
+    }
+
+    #[test]
+    fn can_move() {
+        let board = Board::new_empty(4)
+            .wall_enclosure()
+            .set_obstacles(vec![Position::new(2, 3)]);
+        let pos = Position::new(1, 1);
+        assert!(board.can_move(pos, Direction::Right, &[]));
+        assert!(!board.can_move(pos, Direction::Right, &[Position::new(2, 1)]));
+        assert!(board.can_move(pos, Direction::Right, &[Position::new(3, 1)]));
+        assert!(!board.can_move(Position::new(0, 2), Direction::Left, &[]));
+        assert!(!board.can_move(Position::new(2, 2), Direction::Down, &[]));
+    }
+
+    #[test]
+    fn slide() {
+        let (pos, board) = create_board();
+        assert_eq!(
+            board.slide(pos[Robot::Red], Direction::Right, &pos),
+            (Position::new(4, 1), 4)
+        );
+        assert_eq!(
+            board.slide(pos[Robot::Red], Direction::Left, &pos),
+            (pos[Robot::Red], 0)
+        );
+        assert_eq!(
+            board.slide(Position::new(7, 3), Direction::Up, &pos),
+            (Position::new(7, 2), 1)
+        );
+    }
+
+    #[test]
+    fn target_in_corner() {
+        let mut game = Game::new_enclosed(8);
+        let target = Target::Green(Symbol::Square);
+        let pos = Position::new(2, 5);
+        game.add_target_with_corner(target, pos, Corner::UpperLeft);
+
+        assert_eq!(game.get_target_position(&target), Some(pos));
+        let board = game.board();
+        assert!(board.is_adjacent_to_wall(pos, Direction::Up));
+        assert!(board.is_adjacent_to_wall(pos, Direction::Left));
+        assert!(!board.is_adjacent_to_wall(pos, Direction::Down));
+        assert!(!board.is_adjacent_to_wall(pos, Direction::Right));
+        assert!(board.stoppable_cells(Position::new(2, 7)).contains(&pos));
+
+        let other = Position::new(6, 3);
+        game.add_target_with_corner(target, other, Corner::BottomRight);
+        assert_eq!(game.get_target_position(&target), Some(other));
+        assert!(game.board().is_adjacent_to_wall(other, Direction::Down));
+        assert!(game.board().is_adjacent_to_wall(other, Direction::Right));
+    }
+
+    #[test]
+    fn opposite_directions() {
+        assert_eq!(Direction::Up.opposite(), Direction::Down);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        assert_eq!(Direction::Right.opposite(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        for &dir in DIRECTIONS.iter() {
+            assert_eq!(dir.opposite().opposite(), dir);
+        }
+    }
+
+    #[test]
+    fn standard_targets() {
+        let game = quadrant::game_from_seed(0);
+        assert_eq!(game.target_count(), 17);
+        assert!(game.has_spiral());
+        assert!(game.has_target(&Target::Red(Symbol::Circle)));
+        assert!(!Game::new_empty(16).has_spiral());
+    }
+
+    #[test]
+    fn target_lookup() {
+        let game = quadrant::game_from_seed(0);
+        for (target, pos) in game.iter_targets() {
+            assert_eq!(game.target_at(pos), Some(target));
+        }
+        let board = game.board();
+        let empty = (0..board.side_length())
+            .map(|col| Position::new(col, 0))
+            .find(|&pos| game.iter_targets().all(|(_, target_pos)| target_pos != pos))
+            .unwrap();
+        assert_eq!(game.target_at(empty), None);
+    }
+
+    #[test]
+    fn merge_games() {
+        let standard = quadrant::game_from_seed(0);
+        let mut walls = Game::new(standard.board().clone(), BTreeMap::new());
+        let mut targets = Game::new_empty(16);
+        for (target, pos) in standard.iter_targets() {
+            targets.targets.insert(target, pos);
+        }
+        walls.merge(&targets).unwrap();
+        assert_eq!(walls, standard);
+
+        let mut game = standard.clone();
+        assert_eq!(game.merge(&standard), Ok(()));
+        assert_eq!(game, standard);
+
+        let (target, pos) = standard.iter_targets().next().unwrap();
+        let moved = pos.to_direction(Direction::Right, 16);
+        let mut conflict = Game::new_empty(16);
+        conflict.targets.insert(target, moved);
+        assert!(game.merge(&conflict).is_err());
+
+        let mut conflict = Game::new_empty(16);
+        let other = standard.iter_targets().find(|&(t, _)| t != target).unwrap();
+        conflict.targets.insert(other.0, pos);
+        assert!(game.merge(&conflict).is_err());
+
+        assert!(game.merge(&Game::new_empty(8)).is_err());
+        assert_eq!(game, standard);
+    }
+
+    #[test]
+    fn robot_on_target() {
+        let game = quadrant::game_from_seed(0);
+        let blue_target = Target::Blue(Symbol::Circle);
+        let blue = game.get_target_position(&blue_target).unwrap().into();
+        let spiral = game.get_target_position(&Target::Spiral).unwrap().into();
+
+        let positions = RobotPositions::from_tuples(&[blue, (0, 0), (0, 15), (15, 15)]);
+        assert_eq!(game.robot_on_target(&positions), None);
+
+        let positions = RobotPositions::from_tuples(&[(0, 0), blue, (0, 15), (15, 15)]);
+        assert_eq!(
+            game.robot_on_target(&positions),
+            Some((Robot::Blue, blue_target))
+        );
+
+        let positions = RobotPositions::from_tuples(&[(0, 0), (0, 15), (15, 15), spiral]);
+        assert_eq!(
+            game.robot_on_target(&positions),
+            Some((Robot::Yellow, Target::Spiral))
+        );
+    }
+
     #[test]
     fn move_right() {
         let (mut positions, board) = create_board();
@@ -538,4 +1401,99 @@ mod tests {
         positions = positions.move_in_direction(&board, Robot::Green, Direction::Down);
         assert_eq!(positions[Robot::Green], Position::from((7, 6)));
     }
+
+    #[test]
+    fn symbol_index_round_trip() {
+        for (i, &symbol) in Symbol::all().iter().enumerate() {
+            assert_eq!(symbol.to_index(), i);
+            assert_eq!(Symbol::from_index(i), Some(symbol));
+        }
+        assert_eq!(Symbol::from_index(4), None);
+    }
+
+    #[test]
+    fn target_color_index() {
+        let board = Board::new_empty(4).wall_enclosure();
+        let targets = [
+            Target::Red(Symbol::Circle),
+            Target::Blue(Symbol::Triangle),
+            Target::Green(Symbol::Square),
+            Target::Yellow(Symbol::Hexagon),
+            Target::Spiral,
+        ];
+        for (i, &target) in targets.iter().enumerate() {
+            assert_eq!(target.color_index(), i);
+            let round = Round::new(board.clone(), target, Position::new(1, 1));
+            assert_eq!(round.target_color_index(), i);
+        }
+    }
+
+    #[test]
+    fn idempotent_walls() {
+        let board = Board::new_empty(4)
+            .wall_enclosure()
+            .set_center_walls()
+            .unwrap();
+        assert!(board.is_enclosed());
+        assert_eq!(board.clone().wall_enclosure(), board);
+        assert_eq!(board.clone().set_center_walls(), Ok(board));
+
+        assert!(!Board::new_empty(4).is_enclosed());
+        assert!(Board::new_empty(1).set_center_walls().is_err());
+        assert!(Board::new_empty(0).wall_enclosure().is_enclosed());
+    }
+
+    #[test]
+    fn enclosure_with_gap() {
+        let (_, mut board) = create_board();
+        assert!(board.is_enclosed());
+        board[Position::new(15, 6)].right = false;
+        assert!(!board.is_enclosed());
+
+        let (_, mut board) = create_board();
+        board[Position::new(3, 15)].down = false;
+        assert!(!board.is_enclosed());
+    }
+
+    #[test]
+    fn stoppable_cells() {
+        let board = Board::new_empty(3)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1);
+        let expected = [(0, 0), (0, 2), (2, 0), (2, 2), (1, 0), (1, 2)]
+            .iter()
+            .map(|&pos| Position::from(pos))
+            .collect();
+        assert_eq!(board.stoppable_cells(Position::new(0, 0)), expected);
+    }
+
+    #[test]
+    fn reachable_regions() {
+        let board = Board::new_empty(3).wall_enclosure();
+        let regions = board.reachable_regions();
+        assert_eq!(regions.len(), 4);
+        let corners = [(0, 0), (0, 2), (2, 0), (2, 2)]
+            .iter()
+            .map(|&pos| Position::from(pos))
+            .collect();
+        assert_eq!(regions[0], corners);
+        assert_eq!(
+            regions[2],
+            [(1, 0), (1, 2)]
+                .iter()
+                .map(|&pos| Position::from(pos))
+                .collect()
+        );
+        assert_eq!(regions[3], [Position::new(1, 1)].iter().copied().collect());
+    }
+
+    #[test]
+    fn unreachable_target() {
+        assert!(!quadrant::game_from_seed(0).has_unreachable_target());
+
+        let mut targets = BTreeMap::new();
+        targets.insert(Target::Spiral, Position::new(1, 1));
+        let game = Game::new(Board::new_empty(3).wall_enclosure(), targets);
+        assert!(game.has_unreachable_target());
+    }
 }
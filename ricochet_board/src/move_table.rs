@@ -0,0 +1,116 @@
+//! Precomputed stopping positions to speed up moving robots on the same board many times.
+
+use crate::{Board, Direction, Position, PositionEncoding};
+
+/// Contains the position a robot stops on for each field and direction, if there are no other
+/// robots on the board.
+///
+/// Walls and obstacles are taken into account. Created by
+/// [`Board::build_move_table`](Board::build_move_table) and used by
+/// [`RobotPositions::move_with_table`](crate::RobotPositions::move_with_table) to replace walking
+/// field by field with a lookup and a check for robots in the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveTable {
+    side_length: PositionEncoding,
+    /// The stopping positions of each field in the order of [`DIRECTIONS`](crate::DIRECTIONS),
+    /// going through the fields column by column.
+    stops: Vec<[Position; 4]>,
+}
+
+impl MoveTable {
+    /// Returns the side length of the board the table has been built for.
+    pub fn side_length(&self) -> PositionEncoding {
+        self.side_length
+    }
+
+    /// Returns the position a robot starting on `from` stops on when moving in `direction`, if
+    /// there are no other robots on the board.
+    #[inline(always)]
+    pub fn stop(&self, from: Position, direction: Direction) -> Position {
+        let index = from.column() as usize * self.side_length as usize + from.row() as usize;
+        self.stops[index][direction_index(direction)]
+    }
+}
+
+impl Board {
+    /// Calculates the stopping position of every field and direction, if there are no other
+    /// robots on the board.
+    ///
+    /// Like with [`move_in_direction`](crate::RobotPositions::move_in_direction), a robot moving
+    /// on a row or column without walls stops in front of the field it started on.
+    pub fn build_move_table(&self) -> MoveTable {
+        let side_length = self.side_length();
+        let mut stops = Vec::with_capacity(side_length as usize * side_length as usize);
+
+        for col in 0..side_length {
+            for row in 0..side_length {
+                let from = Position::new(col, row);
+                let stop_in = |direction| {
+                    let mut stop = from;
+                    for _ in 1..side_length {
+                        let next = stop.to_direction(direction, side_length);
+                        if self.is_adjacent_to_wall(stop, direction) || self.is_obstacle(next) {
+                            break;
+                        }
+                        stop = next;
+                    }
+                    stop
+                };
+                stops.push([
+                    stop_in(Direction::Up),
+                    stop_in(Direction::Down),
+                    stop_in(Direction::Right),
+                    stop_in(Direction::Left),
+                ]);
+            }
+        }
+
+        MoveTable { side_length, stops }
+    }
+}
+
+/// Returns the index of `direction` in [`DIRECTIONS`](crate::DIRECTIONS).
+fn direction_index(direction: Direction) -> usize {
+    match direction {
+        Direction::Up => 0,
+        Direction::Down => 1,
+        Direction::Right => 2,
+        Direction::Left => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Direction, Position};
+
+    #[test]
+    fn stops() {
+        let board = Board::new_empty(4)
+            .wall_enclosure()
+            .set_vertical_line(1, 0, 1)
+            .set_obstacles(vec![Position::new(0, 3)]);
+        let table = board.build_move_table();
+
+        assert_eq!(
+            table.stop(Position::new(0, 0), Direction::Right),
+            Position::new(1, 0)
+        );
+        assert_eq!(
+            table.stop(Position::new(0, 0), Direction::Down),
+            Position::new(0, 2)
+        );
+        assert_eq!(
+            table.stop(Position::new(2, 0), Direction::Left),
+            Position::new(2, 0)
+        );
+    }
+
+    #[test]
+    fn no_walls() {
+        let table = Board::new_empty(4).build_move_table();
+        assert_eq!(
+            table.stop(Position::new(1, 1), Direction::Right),
+            Position::new(0, 1)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Reading and writing boards in the text notation used by many online solvers and a compact
+//! drawing of rounds used for tests.
+
+use std::convert::TryFrom;
+
+use crate::{Board, Direction, Position, PositionEncoding, Robot, RobotPositions, Round, Target};
+
+impl Board {
+    /// Creates a board from a string in the notation used by many online solvers.
+    ///
+    /// The first line of the notation contains the side length of the board. Each following line
+    /// lists the walls around one field as `column,row walls`, where `walls` is any combination of
+    /// the letters `N`, `E`, `S` and `W`, e.g. `3,0 NE`. Empty lines are ignored.
+    ///
+    /// Since a wall between two fields is stored only once on a board, walls to the north and west
+    /// of a field are stored as walls at the bottom or to the right of the neighboring field. Walls
+    /// to the north of the first row and to the west of the first column wrap around to the last
+    /// row or column respectively, which is where the board stores its enclosing walls. The same
+    /// wall may be listed on both fields next to it.
+    pub fn from_notation(notation: &str) -> Result<Self, &'static str> {
+        let mut lines = notation
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        let side_length = lines
+            .next()
+            .ok_or("The notation is empty")?
+            .parse::<PositionEncoding>()
+            .map_err(|_| "The first line has to contain the side length of the board")?;
+        if side_length == 0 {
+            return Err("The side length has to be at least 1");
+        }
+        let mut board = Board::new_empty(side_length);
+
+        for line in lines {
+            let (coordinates, walls) = line
+                .split_once(char::is_whitespace)
+                .ok_or("A field has to be followed by its walls")?;
+            let (column, row) = coordinates
+                .split_once(',')
+                .ok_or("A field has to be written as column,row")?;
+            let column = column
+                .parse::<PositionEncoding>()
+                .map_err(|_| "Failed to parse the column of a field")?;
+            let row = row
+                .parse::<PositionEncoding>()
+                .map_err(|_| "Failed to parse the row of a field")?;
+            if column >= side_length || row >= side_length {
+                return Err("A field is outside of the board");
+            }
+            let pos = Position::new(column, row);
+
+            for wall in walls.trim().chars() {
+                match wall.to_ascii_uppercase() {
+                    'N' => board[pos.to_direction(Direction::Up, side_length)].down = true,
+                    'E' => board[pos].right = true,
+                    'S' => board[pos].down = true,
+                    'W' => board[pos.to_direction(Direction::Left, side_length)].right = true,
+                    _ => return Err("Walls have to be one of N, E, S or W"),
+                }
+            }
+        }
+
+        Ok(board)
+    }
+
+    /// Writes the board in the notation read by [`from_notation`](Self::from_notation).
+    ///
+    /// Every wall is written only once as a wall to the east or south of a field. The fields are
+    /// listed row by row.
+    pub fn to_notation(&self) -> String {
+        let side_length = self.side_length();
+        let mut notation = format!("{}\n", side_length);
+
+        for row in 0..side_length {
+            for column in 0..side_length {
+                let field = self[Position::new(column, row)];
+                if !field.right && !field.down {
+                    continue;
+                }
+                let east = if field.right { "E" } else { "" };
+                let south = if field.down { "S" } else { "" };
+                notation.push_str(&format!("{},{} {}{}\n", column, row, east, south));
+            }
+        }
+
+        notation
+    }
+}
+
+/// Creates a round and the starting positions of the robots from a drawing of the board.
+///
+/// Expands to [`Round::from_ascii`](crate::Round::from_ascii) and panics if the drawing is
+/// invalid, which keeps the setup of tests short:
+///
+/// ```
+/// use ricochet_board::{round, Target};
+///
+/// let (round, start) = round!(
+///     Target::Spiral,
+///     "
+///     R . .|.
+///         -
+///     . * . B
+///     G . . Y
+///     . . . .
+///     "
+/// );
+/// assert_eq!(round.board().side_length(), 4);
+/// ```
+#[macro_export]
+macro_rules! round {
+    ($target:expr, $drawing:expr $(,)?) => {
+        $crate::Round::from_ascii($drawing, $target)
+            .expect("Failed to parse the drawing of the round")
+    };
+}
+
+impl Round {
+    /// Creates a round and the starting positions of the robots from a drawing of the board.
+    ///
+    /// Every field is drawn as a single character followed by either a `|` for a wall to its right
+    /// or a space. A line of fields may be followed by a line marking the walls below the fields
+    /// with a `-` under the field, which only contains `-` and spaces. Fields are drawn as
+    /// - `.` for an empty field,
+    /// - `*` for the target position,
+    /// - `R`, `B`, `G` or `Y` for the robot of this color,
+    /// - `r`, `b`, `g` or `y` for a robot standing on the target.
+    ///
+    /// The board is as wide as the first line and has to be square, its edges are enclosed by
+    /// walls. Empty lines at the start and the end are skipped and the indentation of the first
+    /// line is removed from all lines. The [`round!`](crate::round) macro wraps this function.
+    ///
+    /// # Errors
+    /// Returns an error if the drawing isn't square, contains unknown characters or doesn't place
+    /// all robots and the target exactly once.
+    pub fn from_ascii(
+        drawing: &str,
+        target: Target,
+    ) -> Result<(Round, RobotPositions), &'static str> {
+        let lines: Vec<&str> = drawing
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .collect();
+        let lines = &lines[..lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |last| last + 1)];
+        let indent = lines
+            .first()
+            .ok_or("The drawing is empty")?
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(0);
+        let rows: Vec<Vec<char>> = lines
+            .iter()
+            .map(|line| line.chars().skip(indent).collect())
+            .collect();
+
+        let side_length = rows[0].len().div_ceil(2);
+        let mut board = Board::new_empty(
+            PositionEncoding::try_from(side_length).map_err(|_| "The drawing is too large")?,
+        );
+        let mut positions = RobotPositions::default();
+        let mut placed = Vec::with_capacity(4);
+        let mut target_position = None;
+
+        // Pair each line of fields with the line of walls below it, if there is one.
+        let is_wall_line = |line: &[char]| line.iter().all(|&c| c == '-' || c == ' ');
+        let mut field_lines: Vec<(&Vec<char>, Option<&Vec<char>>)> = Vec::new();
+        for line in &rows {
+            match field_lines.last_mut() {
+                Some((_, walls @ None)) if is_wall_line(line) => *walls = Some(line),
+                _ => field_lines.push((line, None)),
+            }
+        }
+        if field_lines.len() != side_length {
+            return Err("The drawing has to be as high as it is wide");
+        }
+
+        for (row, (fields, walls)) in field_lines.into_iter().enumerate() {
+            if fields.len() > side_length * 2 {
+                return Err("A line of fields is longer than the first one");
+            }
+            for column in 0..side_length {
+                let pos = Position::new(column as PositionEncoding, row as PositionEncoding);
+                let field = fields.get(column * 2).copied().unwrap_or(' ');
+                let robot = match field.to_ascii_uppercase() {
+                    '.' | '*' => None,
+                    'R' => Some(Robot::Red),
+                    'B' => Some(Robot::Blue),
+                    'G' => Some(Robot::Green),
+                    'Y' => Some(Robot::Yellow),
+                    _ => return Err("Fields have to be one of ., *, R, B, G or Y"),
+                };
+                if let Some(robot) = robot {
+                    if placed.contains(&robot) {
+                        return Err("A robot is placed more than once");
+                    }
+                    placed.push(robot);
+                    positions.set_robot(robot, pos);
+                }
+                if (field == '*' || field.is_ascii_lowercase())
+                    && target_position.replace(pos).is_some()
+                {
+                    return Err("The target is placed more than once");
+                }
+                match fields.get(column * 2 + 1).copied().unwrap_or(' ') {
+                    '|' => board[pos].right = true,
+                    ' ' => {}
+                    _ => return Err("Walls to the right have to be drawn as |"),
+                }
+                match walls.and_then(|walls| walls.get(column * 2)).copied() {
+                    Some('-') => board[pos].down = true,
+                    Some(' ') | None => {}
+                    _ => return Err("Walls below a field have to be drawn as -"),
+                }
+            }
+        }
+        if placed.len() != 4 {
+            return Err("All four robots have to be placed");
+        }
+        let target_position = target_position.ok_or("The target has to be placed")?;
+        Ok((
+            Round::new(board.wall_enclosure(), target, target_position),
+            positions,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{quadrant, Board, Position, RobotPositions, Round, Target};
+
+    #[test]
+    fn neighboring_walls() {
+        let board = Board::from_notation("3\n0,0 NW\n1,1 E\n2,1 W\n1,2 N\n").unwrap();
+        let expected = Board::new_empty(3)
+            .set_horizontal_line(0, 2, 1)
+            .set_vertical_line(2, 0, 1)
+            .set_vertical_line(1, 1, 1)
+            .set_horizontal_line(1, 1, 1);
+        assert_eq!(board, expected);
+        assert_eq!(board.to_notation(), "3\n2,0 E\n1,1 ES\n0,2 S\n");
+    }
+
+    #[test]
+    fn duplicate_walls() {
+        let single = Board::from_notation("4\n1,1 ES\n3,2 E\n").unwrap();
+        let duplicate = Board::from_notation("4\n1,1 ES\n2,1 W\n1,2 N\n3,2 E\n0,2 W\n").unwrap();
+        assert_eq!(single, duplicate);
+        assert_eq!(duplicate.to_notation(), single.to_notation());
+    }
+
+    #[test]
+    fn round_trip() {
+        let board = quadrant::game_from_seed(0).board().clone();
+        assert_eq!(Board::from_notation(&board.to_notation()), Ok(board));
+    }
+
+    #[test]
+    fn invalid_notation() {
+        assert!(Board::from_notation("").is_err());
+        assert!(Board::from_notation("2\n2,0 N").is_err());
+        assert!(Board::from_notation("2\n1,0 X").is_err());
+        assert!(Board::from_notation("2\n1 N").is_err());
+    }
+
+    #[test]
+    fn ascii_round() {
+        let (round, positions) = Round::from_ascii(
+            "
+            R . .|.
+                -
+            . * . B
+                  -
+            G . . .
+            -
+            . . Y .
+            ",
+            Target::Spiral,
+        )
+        .unwrap();
+
+        let expected = Board::new_empty(4)
+            .wall_enclosure()
+            .set_vertical_line(2, 0, 1)
+            .set_horizontal_line(2, 0, 1)
+            .set_horizontal_line(3, 1, 1)
+            .set_horizontal_line(0, 2, 1);
+        assert_eq!(round.board(), &expected);
+        assert_eq!(round.target_position(), Position::new(1, 1));
+        assert_eq!(
+            positions,
+            RobotPositions::from_tuples(&[(0, 0), (3, 1), (0, 2), (2, 3)])
+        );
+    }
+
+    #[test]
+    fn invalid_ascii_round() {
+        let target = Target::Spiral;
+        assert!(Round::from_ascii("", target).is_err());
+        assert!(Round::from_ascii("R B\nG Y", target).is_err());
+        assert!(Round::from_ascii("R B\n* Y", target).is_err());
+        assert!(Round::from_ascii("R B\nG *\n\n. Y", target).is_err());
+        assert!(Round::from_ascii("R B\n- -\n-\nG Y", target).is_err());
+        assert!(Round::from_ascii("R *\nR Y", target).is_err());
+        assert!(Round::from_ascii("R *\nG y", target).is_err());
+        let (round, start) = Round::from_ascii("R b\nG|Y", target).unwrap();
+        assert!(round.target_reached(&start));
+    }
+}
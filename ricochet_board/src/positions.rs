@@ -1,15 +1,28 @@
 use itertools::Itertools;
+use std::collections::HashMap;
 use std::{fmt, mem, ops};
 
-use crate::{Board, Direction, Robot, DIRECTIONS, ROBOTS};
+use crate::{Board, Direction, MoveTable, Robot, DIRECTIONS, ROBOTS};
 
 /// The type a position is encoded as.
 ///
 /// Depending on the number of bits in a value, different positions on a board can be encoded. A u8
 /// is sufficient to encode any position on the standard board. Using u64 would allow encoding
 /// positions on a 2^32x2^32 board, see [Position] for more information.
+///
+/// Defaults to `u16`, which supports boards up to 256x256. Larger boards can be used by enabling
+/// the `u32-position` or `u64-position` feature.
+#[cfg(not(any(feature = "u32-position", feature = "u64-position")))]
 pub type PositionEncoding = u16;
 
+/// The type a position is encoded as, enabled by the `u32-position` feature.
+#[cfg(all(feature = "u32-position", not(feature = "u64-position")))]
+pub type PositionEncoding = u32;
+
+/// The type a position is encoded as, enabled by the `u64-position` feature.
+#[cfg(feature = "u64-position")]
+pub type PositionEncoding = u64;
+
 /// A position on the board.
 ///
 /// ```txt
@@ -30,6 +43,19 @@ pub struct RobotPositions {
     yellow: Position,
 }
 
+/// A move of a single robot which changed its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveResult {
+    /// The robot which has been moved.
+    pub robot: Robot,
+    /// The direction the robot moved in.
+    pub direction: Direction,
+    /// The position of the robot before the move.
+    pub from: Position,
+    /// The position of the robot after the move.
+    pub to: Position,
+}
+
 impl Position {
     /// Number of bits used for the encoding.
     const BIT_COUNT: PositionEncoding = mem::size_of::<PositionEncoding>() as PositionEncoding * 8;
@@ -38,18 +64,7 @@ impl Position {
     ///
     /// The first half of the bits is `0` the rest `1`. This would be `0000_1111` for `u8`
     /// or `0000_0000_1111_1111` for `u16`.
-    const ROW_FLAG: PositionEncoding = {
-        // When 1.50 is stabilized, this will be possible.
-        // Currently requires the `const_int_pow` feature.
-        // (2 as PositionEncoding).pow((Position::BIT_COUNT / 2) as u32) - 1
-
-        let mut flag: PositionEncoding = 1;
-        // Add more ones until half the bits are ones.
-        while flag.count_ones() < mem::size_of::<PositionEncoding>() as u32 * 8 / 2 {
-            flag = (flag << 1) + 1;
-        }
-        flag
-    };
+    const ROW_FLAG: PositionEncoding = PositionEncoding::MAX >> (Self::BIT_COUNT / 2);
 
     /// Bitflag used to extract the column information of a position by removing the row bits.
     ///
@@ -101,6 +116,25 @@ impl Position {
         };
         self
     }
+
+    /// Returns the orthogonally adjacent fields together with the direction they lie in.
+    ///
+    /// Unlike [`to_direction`](Position::to_direction) this doesn't wrap around, so positions on
+    /// the edge of a board with `side_length` have less than four neighbors.
+    pub fn neighbors(
+        self,
+        side_length: PositionEncoding,
+    ) -> impl Iterator<Item = (Direction, Position)> {
+        DIRECTIONS
+            .iter()
+            .filter(move |&&direction| match direction {
+                Direction::Up => self.row() > 0,
+                Direction::Down => self.row() + 1 < side_length,
+                Direction::Right => self.column() + 1 < side_length,
+                Direction::Left => self.column() > 0,
+            })
+            .map(move |&direction| (direction, self.to_direction(direction, side_length)))
+    }
 }
 
 impl fmt::Debug for Position {
@@ -134,6 +168,20 @@ impl RobotPositions {
         }
     }
 
+    /// Creates the positions from a map of each robot to its position.
+    ///
+    /// Returns the first missing robot in the order red, blue, green, yellow as an error if not all
+    /// robots are contained in `map`.
+    pub fn from_map(map: &HashMap<Robot, Position>) -> Result<Self, Robot> {
+        let get = |robot| map.get(&robot).copied().ok_or(robot);
+        Ok(RobotPositions {
+            red: get(Robot::Red)?,
+            blue: get(Robot::Blue)?,
+            green: get(Robot::Green)?,
+            yellow: get(Robot::Yellow)?,
+        })
+    }
+
     /// Returns the positions of the robots as an array in the order `[red, blue, green, yellow]`.
     pub fn to_array(&self) -> [Position; 4] {
         [self.red, self.blue, self.green, self.yellow]
@@ -155,7 +203,7 @@ impl RobotPositions {
     }
 
     /// Sets the `robot` to `new_position`.
-    fn set_robot(&mut self, robot: Robot, new_position: Position) {
+    pub(crate) fn set_robot(&mut self, robot: Robot, new_position: Position) {
         *match robot {
             Robot::Red => &mut self.red,
             Robot::Blue => &mut self.blue,
@@ -181,11 +229,30 @@ impl RobotPositions {
         }
     }
 
+    /// Returns the robots whose positions differ between `self` and `other`.
+    ///
+    /// The robots are returned in the order red, blue, green, yellow.
+    pub fn differing_robots(&self, other: &Self) -> Vec<Robot> {
+        ROBOTS
+            .iter()
+            .filter(|&&robot| self[robot] != other[robot])
+            .copied()
+            .collect()
+    }
+
     /// Checks if the adjacent field in the direction is reachable, i.e. no wall in between and not
-    /// already occupied.
-    fn adjacent_reachable(&self, board: &Board, pos: Position, direction: Direction) -> bool {
-        !board.is_adjacent_to_wall(pos, direction)
-            && !self.contains_any_robot(pos.to_direction(direction, board.side_length()))
+    /// already occupied by a robot or an obstacle.
+    pub(crate) fn adjacent_reachable(
+        &self,
+        board: &Board,
+        pos: Position,
+        direction: Direction,
+    ) -> bool {
+        if board.is_adjacent_to_wall(pos, direction) {
+            return false;
+        }
+        let next = pos.to_direction(direction, board.side_length());
+        !self.contains_any_robot(next) && !board.is_obstacle(next)
     }
 
     /// Creates an Iterator over all positions reachable in one move that differ from `self`.
@@ -198,31 +265,145 @@ impl RobotPositions {
             .iter()
             .cartesian_product(DIRECTIONS.iter())
             .filter_map(move |(&robot, &direction)| {
-                Some(
-                    initial_pos
-                        .clone()
-                        .move_in_direction(board, robot, direction),
-                )
-                .filter(|pos| pos != &initial_pos)
-                .map(|pos| (pos, (robot, direction)))
+                initial_pos
+                    .clone()
+                    .try_move_in_direction(board, robot, direction)
+                    .ok()
+                    .map(|pos| (pos, (robot, direction)))
+            })
+    }
+
+    /// Creates an Iterator over all moves of a single robot which change its position.
+    ///
+    /// Like [`reachable_positions`](Self::reachable_positions), moves of robots blocked by a wall,
+    /// an obstacle or another robot are skipped.
+    pub fn legal_moves<'a>(&'a self, board: &'a Board) -> impl Iterator<Item = MoveResult> + 'a {
+        self.reachable_positions(board)
+            .map(move |(positions, (robot, direction))| MoveResult {
+                robot,
+                direction,
+                from: self[robot],
+                to: positions[robot],
             })
     }
 
     /// Moves `robot` as far in the given `direction` as possible.
     pub fn move_in_direction(mut self, board: &Board, robot: Robot, direction: Direction) -> Self {
-        // start form the current position
-        let mut temp_pos = self[robot];
+        let (stop, _) = board.slide(self[robot], direction, &self);
+        self.set_robot(robot, stop);
+
+        self
+    }
 
-        // check if the next position is reachable from the temporary position
-        while self.adjacent_reachable(board, temp_pos, direction) {
-            temp_pos = temp_pos.to_direction(direction, board.side_length());
+    /// Returns the position `mover` stops on when moving in `direction`, if it is stopped by
+    /// another robot.
+    ///
+    /// Returns `None` if the move ends in front of a wall or an obstacle instead. If `mover` is
+    /// already next to a robot, its current position is returned.
+    pub fn blocking_cell(
+        &self,
+        mover: Robot,
+        direction: Direction,
+        board: &Board,
+    ) -> Option<Position> {
+        let stop = self.clone().move_in_direction(board, mover, direction)[mover];
+        if board.is_adjacent_to_wall(stop, direction) {
+            return None;
         }
+        let next = stop.to_direction(direction, board.side_length());
+        if next != self[mover] && self.contains_any_robot(next) {
+            Some(stop)
+        } else {
+            None
+        }
+    }
 
-        // set the robot to the last possible position
-        self.set_robot(robot, temp_pos);
+    /// Moves `robot` as far in the given `direction` as possible using the precomputed stopping
+    /// positions in `table`.
+    ///
+    /// Returns the same positions as [`move_in_direction`](Self::move_in_direction) if `table` has
+    /// been built for the board the robots are on.
+    pub fn move_with_table(
+        mut self,
+        table: &MoveTable,
+        robot: Robot,
+        direction: Direction,
+    ) -> Self {
+        let side_length = table.side_length();
+        let from = self[robot];
+        let stop = table.stop(from, direction);
+
+        // The number of fields between `from` and `pos` if `pos` is in `direction` of `from`.
+        let distance = |pos: Position| match direction {
+            Direction::Right if pos.row() == from.row() => {
+                Some((pos.column() + side_length - from.column()) % side_length)
+            }
+            Direction::Left if pos.row() == from.row() => {
+                Some((from.column() + side_length - pos.column()) % side_length)
+            }
+            Direction::Down if pos.column() == from.column() => {
+                Some((pos.row() + side_length - from.row()) % side_length)
+            }
+            Direction::Up if pos.column() == from.column() => {
+                Some((from.row() + side_length - pos.row()) % side_length)
+            }
+            _ => None,
+        };
+        let stop_distance = distance(stop).expect("The stop has to be in the moved direction");
 
+        // Stop in front of the closest robot between `from` and `stop`.
+        let blocking = ROBOTS
+            .iter()
+            .filter(|&&other| other != robot)
+            .filter_map(|&other| distance(self[other]).map(|dist| (dist, self[other])))
+            .filter(|&(dist, _)| dist > 0 && dist <= stop_distance)
+            .min_by_key(|&(dist, _)| dist);
+        let new_position = match blocking {
+            Some((_, blocking_pos)) => blocking_pos.to_direction(direction.opposite(), side_length),
+            None => stop,
+        };
+
+        self.set_robot(robot, new_position);
         self
     }
+
+    /// Creates an Iterator over all positions reachable in one move that differ from `self` like
+    /// [`reachable_positions`](Self::reachable_positions), using the stopping positions in
+    /// `table`.
+    pub fn reachable_positions_with_table<'a>(
+        &self,
+        table: &'a MoveTable,
+    ) -> impl Iterator<Item = (RobotPositions, (Robot, Direction))> + 'a {
+        let initial_pos = self.clone();
+        ROBOTS
+            .iter()
+            .cartesian_product(DIRECTIONS.iter())
+            .filter_map(move |(&robot, &direction)| {
+                let moved = initial_pos.clone().move_with_table(table, robot, direction);
+                if moved[robot] == initial_pos[robot] {
+                    None
+                } else {
+                    Some((moved, (robot, direction)))
+                }
+            })
+    }
+
+    /// Moves `robot` as far in the given `direction` as possible.
+    ///
+    /// Returns `Ok` with the new positions if the robot moved and `Err` with the unchanged
+    /// positions if the robot is blocked by a wall or another robot.
+    pub fn try_move_in_direction(
+        self,
+        board: &Board,
+        robot: Robot,
+        direction: Direction,
+    ) -> Result<Self, Self> {
+        if self.adjacent_reachable(board, self[robot], direction) {
+            Ok(self.move_in_direction(board, robot, direction))
+        } else {
+            Err(self)
+        }
+    }
 }
 
 impl ops::Index<Robot> for RobotPositions {
@@ -238,6 +419,17 @@ impl ops::Index<Robot> for RobotPositions {
     }
 }
 
+impl Default for RobotPositions {
+    /// Places the robots in the corners of a [standard board](crate::quadrant::STANDARD_BOARD_SIZE),
+    /// red in the upper left and the others following clockwise.
+    ///
+    /// The positions are only distinct and on the board if it is at least 16 fields wide.
+    fn default() -> Self {
+        let last = crate::quadrant::STANDARD_BOARD_SIZE - 1;
+        RobotPositions::from_tuples(&[(0, 0), (last, 0), (last, last), (0, last)])
+    }
+}
+
 impl fmt::Debug for RobotPositions {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -267,8 +459,12 @@ impl fmt::Display for RobotPositions {
 
 #[cfg(test)]
 mod tests {
-    use super::Position;
-    use crate::{Board, Direction, PositionEncoding, Robot, RobotPositions};
+    use super::{MoveResult, Position};
+    use crate::{
+        quadrant, Board, Direction, PositionEncoding, Robot, RobotPositions, DIRECTIONS, ROBOTS,
+    };
+    use itertools::Itertools;
+    use std::collections::HashMap;
 
     #[test]
     fn check_flags() {
@@ -278,6 +474,20 @@ mod tests {
         assert_eq!(!row_flag, Position::COLUMN_FLAG);
     }
 
+    #[test]
+    fn neighbors() {
+        let corner = Position::new(0, 15).neighbors(16).collect::<Vec<_>>();
+        assert_eq!(
+            corner,
+            vec![
+                (Direction::Up, Position::new(0, 14)),
+                (Direction::Right, Position::new(1, 15)),
+            ]
+        );
+        assert_eq!(Position::new(0, 5).neighbors(16).count(), 3);
+        assert_eq!(Position::new(7, 5).neighbors(16).count(), 4);
+    }
+
     #[test]
     fn reachable_positions() {
         let board = Board::new_empty(16).wall_enclosure();
@@ -307,4 +517,156 @@ mod tests {
             &expected
         );
     }
+
+    #[test]
+    fn legal_moves() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let starting_pos = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        let moves = starting_pos.legal_moves(&board).collect::<Vec<_>>();
+        assert_eq!(moves.len(), 4);
+        assert_eq!(
+            moves[0],
+            MoveResult {
+                robot: Robot::Blue,
+                direction: Direction::Right,
+                from: Position::new(1, 0),
+                to: Position::new(15, 0),
+            }
+        );
+        assert!(moves.iter().all(|mov| mov.from != mov.to));
+    }
+
+    #[test]
+    fn move_with_table() {
+        let boards = [
+            quadrant::game_from_seed(7).board().clone(),
+            Board::new_empty(16).set_obstacles(vec![Position::new(3, 4)]),
+        ];
+        let starting_pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+
+        for board in boards.iter() {
+            let table = board.build_move_table();
+            for &robot in ROBOTS.iter() {
+                for &direction in DIRECTIONS.iter() {
+                    assert_eq!(
+                        starting_pos
+                            .clone()
+                            .move_with_table(&table, robot, direction),
+                        starting_pos
+                            .clone()
+                            .move_in_direction(board, robot, direction)
+                    );
+                }
+            }
+            assert_eq!(
+                starting_pos
+                    .reachable_positions_with_table(&table)
+                    .collect::<Vec<_>>(),
+                starting_pos.reachable_positions(board).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn default_positions() {
+        let pos = RobotPositions::default();
+        assert_eq!(pos[Robot::Red], Position::new(0, 0));
+        assert_eq!(pos[Robot::Green], Position::new(15, 15));
+        assert!(pos.to_array().iter().all_unique());
+    }
+
+    #[test]
+    fn from_map() {
+        let mut map = HashMap::new();
+        map.insert(Robot::Red, Position::new(0, 0));
+        map.insert(Robot::Blue, Position::new(1, 0));
+        map.insert(Robot::Yellow, Position::new(1, 1));
+        assert_eq!(RobotPositions::from_map(&map), Err(Robot::Green));
+
+        map.insert(Robot::Green, Position::new(0, 1));
+        let expected = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert_eq!(RobotPositions::from_map(&map), Ok(expected));
+    }
+
+    #[test]
+    fn differing_robots() {
+        let first = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        let second = RobotPositions::from_tuples(&[(0, 0), (15, 0), (0, 1), (1, 15)]);
+        assert_eq!(first.differing_robots(&first), vec![]);
+        assert_eq!(
+            first.differing_robots(&second),
+            vec![Robot::Blue, Robot::Yellow]
+        );
+    }
+
+    #[test]
+    fn try_move_in_direction() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let starting_pos = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        assert_eq!(
+            starting_pos
+                .clone()
+                .try_move_in_direction(&board, Robot::Red, Direction::Right),
+            Err(starting_pos.clone())
+        );
+        assert_eq!(
+            starting_pos
+                .clone()
+                .try_move_in_direction(&board, Robot::Blue, Direction::Right),
+            Ok(RobotPositions::from_tuples(&[
+                (0, 0),
+                (15, 0),
+                (0, 1),
+                (1, 1)
+            ]))
+        );
+    }
+
+    #[test]
+    fn stop_at_obstacle() {
+        let board = Board::new_empty(16)
+            .wall_enclosure()
+            .set_obstacles(vec![Position::new(10, 0)]);
+        let starting_pos = RobotPositions::from_tuples(&[(0, 0), (0, 5), (0, 6), (0, 7)]);
+
+        let moved = starting_pos.move_in_direction(&board, Robot::Red, Direction::Right);
+        assert_eq!(moved[Robot::Red], Position::new(9, 0));
+
+        let blocked = moved.clone();
+        assert_eq!(
+            blocked.try_move_in_direction(&board, Robot::Red, Direction::Right),
+            Err(moved.clone())
+        );
+        assert!(moved
+            .reachable_positions(&board)
+            .all(|(pos, _)| !pos.to_array().contains(&Position::new(10, 0))));
+    }
+
+    #[test]
+    fn blocking_cell() {
+        let board = Board::new_empty(8)
+            .wall_enclosure()
+            .set_obstacles(vec![Position::new(5, 5)]);
+        let pos = RobotPositions::from_tuples(&[(0, 0), (4, 0), (5, 1), (0, 5)]);
+
+        assert_eq!(
+            pos.blocking_cell(Robot::Red, Direction::Right, &board),
+            Some(Position::new(3, 0))
+        );
+        assert_eq!(pos.blocking_cell(Robot::Red, Direction::Left, &board), None);
+        assert_eq!(
+            pos.blocking_cell(Robot::Red, Direction::Down, &board),
+            Some(Position::new(0, 4))
+        );
+        assert_eq!(
+            pos.blocking_cell(Robot::Green, Direction::Down, &board),
+            None
+        );
+        assert_eq!(
+            pos.blocking_cell(Robot::Yellow, Direction::Up, &board),
+            Some(Position::new(0, 1))
+        );
+    }
 }
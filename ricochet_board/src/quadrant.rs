@@ -3,10 +3,14 @@
 //! These quadrants are the same as the ones used to build the physical board.
 
 use draw_a_box::{find_character, Weight};
+use rand::{Rng, SeedableRng};
 use std::fmt;
 
 use crate::draw::{FIELD_DRAW_HEIGHT, FIELD_DRAW_WIDTH};
-use crate::{Field, Game, PositionEncoding, Round, Symbol, Target, TARGETS};
+use crate::{
+    Board, Field, Game, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target, Walls,
+    TARGETS,
+};
 
 /// The side length of the standard physical board.
 pub const STANDARD_BOARD_SIZE: PositionEncoding = 16;
@@ -103,6 +107,28 @@ impl fmt::Display for QuadColor {
     }
 }
 
+impl From<QuadColor> for Robot {
+    fn from(color: QuadColor) -> Self {
+        match color {
+            QuadColor::Green => Robot::Green,
+            QuadColor::Red => Robot::Red,
+            QuadColor::Blue => Robot::Blue,
+            QuadColor::Yellow => Robot::Yellow,
+        }
+    }
+}
+
+impl From<Robot> for QuadColor {
+    fn from(robot: Robot) -> Self {
+        match robot {
+            Robot::Green => QuadColor::Green,
+            Robot::Red => QuadColor::Red,
+            Robot::Blue => QuadColor::Blue,
+            Robot::Yellow => QuadColor::Yellow,
+        }
+    }
+}
+
 /// The directions a [`Field`](super::Field) stores walls for.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum WallDirection {
@@ -113,7 +139,7 @@ pub enum WallDirection {
 }
 
 impl WallDirection {
-    /// Changes the direction of a wall when rotating a quadrant.
+    /// Changes the direction of a wall when rotating or mirroring a quadrant.
     fn rotate(self) -> Self {
         match self {
             WallDirection::Down => WallDirection::Right,
@@ -193,6 +219,81 @@ impl BoardQuadrant {
         }
     }
 
+    /// Reflects the quadrant across its main diagonal.
+    ///
+    /// The columns and rows of all walls and targets are swapped and walls to the right become
+    /// walls at the bottom and vice versa. A quadrant in the upper right is moved to the bottom
+    /// left and vice versa, the other orientations are kept.
+    pub fn mirror(&mut self) {
+        self.orientation = match self.orientation {
+            Orientation::UpperRight => Orientation::BottomLeft,
+            Orientation::BottomLeft => Orientation::UpperRight,
+            orientation => orientation,
+        };
+
+        self.walls = self
+            .walls
+            .iter()
+            .map(|&((c, r), dir)| ((r, c), dir.rotate()))
+            .collect();
+
+        self.targets = self
+            .targets
+            .iter()
+            .map(|&((c, r), t)| ((r, c), t))
+            .collect();
+    }
+
+    /// Creates an enclosed 8x8 board containing only the walls of this quadrant.
+    ///
+    /// The field of the quadrant which is part of the center block on a complete board is
+    /// enclosed by walls as well. This makes it possible to solve rounds on a single quadrant.
+    pub fn to_board(&self) -> Board {
+        let size = (STANDARD_BOARD_SIZE / 2) as usize;
+        let fields = self.wall_fields();
+        let mut walls = vec![vec![Field::default(); size]; size];
+
+        // Walls in the first column and row of `fields` wrap around to the opposite side.
+        for (c, col) in fields.iter().enumerate() {
+            for (r, field) in col.iter().enumerate() {
+                let board_field = &mut walls[(c + size - 1) % size][(r + size - 1) % size];
+                board_field.down |= field.down;
+                board_field.right |= field.right;
+            }
+        }
+
+        let last = STANDARD_BOARD_SIZE / 2 - 1;
+        let (center_col, center_row) = match self.orientation {
+            Orientation::UpperLeft => (last, last),
+            Orientation::UpperRight => (0, last),
+            Orientation::BottomRight => (0, 0),
+            Orientation::BottomLeft => (last, 0),
+        };
+
+        Board::new(walls)
+            .wall_enclosure()
+            .enclose_lengths(center_col, center_row, 1, 1)
+    }
+
+    /// Creates a 9x9 grid containing the walls of the quadrant.
+    ///
+    /// The quadrant is shifted by one field to the right and down, since rotated quadrants can
+    /// contain walls in column or row `-1`.
+    fn wall_fields(&self) -> Walls {
+        let size = QUADRANT_SIZE as usize;
+        let mut fields = vec![vec![Field::default(); size]; size];
+
+        for ((c, r), d) in &self.walls {
+            let field = &mut fields[(c + 1) as usize][(r + 1) as usize];
+            match d {
+                WallDirection::Down => field.down = true,
+                WallDirection::Right => field.right = true,
+            }
+        }
+
+        fields
+    }
+
     /// Creates a default quadrant of `color` in the upper left with no walls or targets.
     fn default_quadrant(color: QuadColor) -> Self {
         BoardQuadrant {
@@ -220,17 +321,7 @@ impl BoardQuadrant {
 
 impl fmt::Display for BoardQuadrant {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let size = QUADRANT_SIZE as usize;
-        let mut print = vec![vec![Field::default(); size]; size];
-
-        for ((c, r), d) in &self.walls {
-            let field = &mut print[(c + 1) as usize][(r + 1) as usize];
-            match d {
-                WallDirection::Down => field.down = true,
-                WallDirection::Right => field.right = true,
-            }
-        }
-
+        let print = self.wall_fields();
         let (mut canvas, mut weights) = crate::draw::create_board_string_vec(&print);
         let mut output = String::new();
 
@@ -264,7 +355,51 @@ pub fn round_from_seed(seed: usize) -> Round {
     let seed = seed % DISTINCT_STANDARD_ROUNDS;
     let target_count = TARGETS.len();
     let game = game_from_seed(seed / target_count);
-    let target = num_to_target(seed % target_count);
+    round_from_game(&game, seed % target_count)
+}
+
+/// Creates a `Round` with [`round_from_seed`](round_from_seed) and starting positions of the
+/// robots from `start_seed`.
+///
+/// The robots are placed on distinct fields outside of the walled off center, so the round can be
+/// solved. The same pair of seeds always creates the same round and positions.
+pub fn round_and_start_from_seed(round_seed: usize, start_seed: u64) -> (Round, RobotPositions) {
+    let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(start_seed);
+    let center = STANDARD_BOARD_SIZE / 2 - 1..=STANDARD_BOARD_SIZE / 2;
+
+    let mut positions: Vec<(PositionEncoding, PositionEncoding)> = Vec::with_capacity(4);
+    while positions.len() < 4 {
+        let pos = (
+            rng.gen_range(0..STANDARD_BOARD_SIZE),
+            rng.gen_range(0..STANDARD_BOARD_SIZE),
+        );
+        let in_center = center.contains(&pos.0) && center.contains(&pos.1);
+        if in_center || positions.contains(&pos) {
+            continue;
+        }
+        positions.push(pos);
+    }
+
+    let start =
+        RobotPositions::from_tuples(&[positions[0], positions[1], positions[2], positions[3]]);
+    (round_from_seed(round_seed), start)
+}
+
+/// Creates an iterator over all [8262](DISTINCT_STANDARD_ROUNDS) distinct rounds.
+///
+/// The rounds are yielded in the same order as created by [`round_from_seed`](round_from_seed),
+/// but each game is only created once for all of its targets.
+pub fn all_standard_rounds() -> impl Iterator<Item = Round> {
+    let quadrants = gen_quadrants();
+    (0..DISTINCT_STANDARD_BOARDS).flat_map(move |seed| {
+        let game = game_from_seed_with_quadrants(seed, &quadrants);
+        (0..TARGETS.len()).map(move |n| round_from_game(&game, n))
+    })
+}
+
+/// Creates a `Round` on the board of `game` with the target `num_to_target(n)`.
+fn round_from_game(game: &Game, n: usize) -> Round {
+    let target = num_to_target(n);
     Round::new(
         game.board().clone(),
         target,
@@ -280,6 +415,12 @@ pub fn round_from_seed(seed: usize) -> Round {
 /// The actual seed used is the given `seed` mod `DISTINCT_STANDARD_BOARDS` to ensure its in the
 /// correct range.
 pub fn game_from_seed(seed: usize) -> Game {
+    game_from_seed_with_quadrants(seed, &gen_quadrants())
+}
+
+/// Creates a `Game` from a seed like [`game_from_seed`](game_from_seed), choosing from the
+/// already generated `quadrants`.
+fn game_from_seed_with_quadrants(seed: usize, quadrants: &[BoardQuadrant]) -> Game {
     let seed = seed % DISTINCT_STANDARD_BOARDS;
     let mut indices = Vec::new();
     let mut div_mod = |i: usize, div: usize| {
@@ -292,7 +433,6 @@ pub fn game_from_seed(seed: usize) -> Game {
         div = div_mod(div, *denom);
     }
 
-    let quadrants = gen_quadrants();
     let mut chosen_quads = Vec::with_capacity(4);
 
     // Choose a red quadrant for the upper left piece.
@@ -315,35 +455,121 @@ pub fn game_from_seed(seed: usize) -> Game {
     Game::from_quadrants(&chosen_quads)
 }
 
+/// Returns the quadrant with the given `id`, e.g. `"Red1"` or `"yellow3"`.
+///
+/// An id consists of the name of the quadrant's color followed by its number from 1 to 3 in the
+/// order of [`gen_quadrants`](gen_quadrants). The case of the color is ignored. Returns `None` if
+/// there is no quadrant with this id.
+pub fn quadrant_from_id(id: &str) -> Option<BoardQuadrant> {
+    let split = id.find(|c: char| c.is_ascii_digit())?;
+    let (color, number) = id.split_at(split);
+    let color = match color.to_lowercase().as_str() {
+        "red" => QuadColor::Red,
+        "blue" => QuadColor::Blue,
+        "green" => QuadColor::Green,
+        "yellow" => QuadColor::Yellow,
+        _ => return None,
+    };
+    let number = number.parse::<usize>().ok()?.checked_sub(1)?;
+    gen_quadrants()
+        .into_iter()
+        .filter(|quad| quad.color() == color)
+        .nth(number)
+}
+
+/// Assembles a standard board from the ids of the quadrants in each orientation.
+///
+/// This is the programmatic equivalent of putting the physical quadrants together. The ids are
+/// parsed with [`quadrant_from_id`](quadrant_from_id) and each quadrant is rotated to the
+/// orientation it has been chosen for.
+///
+/// ```
+/// use ricochet_board::quadrant::StandardBoardBuilder;
+///
+/// let game = StandardBoardBuilder::new()
+///     .upper_left("Red1")
+///     .upper_right("Blue2")
+///     .bottom_right("Green1")
+///     .bottom_left("Yellow3")
+///     .build()
+///     .unwrap();
+/// assert_eq!(game.board().side_length(), 16);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StandardBoardBuilder {
+    /// The chosen ids in the order of [`ORIENTATIONS`](ORIENTATIONS).
+    ids: [Option<String>; 4],
+}
+
+impl StandardBoardBuilder {
+    /// Creates a builder without any chosen quadrants.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chooses the quadrant with `id` for the upper left.
+    pub fn upper_left(self, id: &str) -> Self {
+        self.quadrant(Orientation::UpperLeft, id)
+    }
+
+    /// Chooses the quadrant with `id` for the upper right.
+    pub fn upper_right(self, id: &str) -> Self {
+        self.quadrant(Orientation::UpperRight, id)
+    }
+
+    /// Chooses the quadrant with `id` for the bottom right.
+    pub fn bottom_right(self, id: &str) -> Self {
+        self.quadrant(Orientation::BottomRight, id)
+    }
+
+    /// Chooses the quadrant with `id` for the bottom left.
+    pub fn bottom_left(self, id: &str) -> Self {
+        self.quadrant(Orientation::BottomLeft, id)
+    }
+
+    /// Chooses the quadrant with `id` for `orientation`.
+    pub fn quadrant(mut self, orientation: Orientation, id: &str) -> Self {
+        let index = ORIENTATIONS.iter().position(|&o| o == orientation).unwrap();
+        self.ids[index] = Some(id.to_string());
+        self
+    }
+
+    /// Creates the game from the chosen quadrants.
+    ///
+    /// Returns an error if a quadrant hasn't been chosen, an id is unknown or two quadrants have
+    /// the same color.
+    pub fn build(&self) -> Result<Game, &'static str> {
+        let mut quads: Vec<BoardQuadrant> = Vec::with_capacity(4);
+        for (id, &orientation) in self.ids.iter().zip(ORIENTATIONS.iter()) {
+            let id = id.as_ref().ok_or("not all quadrants have been chosen")?;
+            let mut quad = quadrant_from_id(id).ok_or("unknown quadrant id")?;
+            if quads.iter().any(|other| other.color() == quad.color()) {
+                return Err("two quadrants have the same color");
+            }
+            quad.rotate_to(orientation);
+            quads.push(quad);
+        }
+        Ok(Game::from_quadrants(&quads))
+    }
+}
+
 /// Create a target from an integer between 0 and 16 inclusive.
 ///
 /// There are four targets per color
 /// 0 to 3 are the red targets. 4 to 7 are the blue targets, followed by four green, four yellow and finally one spiral.
-/// The symbols are chosen with [num_to_target_symbol].
+/// The symbols are chosen with [Symbol::from_index].
 fn num_to_target(n: usize) -> Target {
+    let symbol = || Symbol::from_index(n % 4).expect("symbol index out of range");
     match n {
-        0..=3 => Target::Red(num_to_target_symbol(n % 4)),
-        4..=7 => Target::Blue(num_to_target_symbol(n % 4)),
-        8..=11 => Target::Green(num_to_target_symbol(n % 4)),
-        12..=15 => Target::Yellow(num_to_target_symbol(n % 4)),
+        0..=3 => Target::Red(symbol()),
+        4..=7 => Target::Blue(symbol()),
+        8..=11 => Target::Green(symbol()),
+        12..=15 => Target::Yellow(symbol()),
         16 => Target::Spiral,
         _ => panic!(),
     }
 }
 
-/// Creates a symbol from an integer between 0 and 3 inclusive.
-///
-/// The ordering is `Circle`, `Triangle`, `Square`, and `Hexagon`.
-fn num_to_target_symbol(n: usize) -> Symbol {
-    match n {
-        0 => Symbol::Circle,
-        1 => Symbol::Triangle,
-        2 => Symbol::Square,
-        3 => Symbol::Hexagon,
-        _ => panic!(),
-    }
-}
-
 /// Creates a vec containing all known quadrants.
 ///
 /// There are three quadrants for each color and the vec contains them in the order red, blue, green, yellow.
@@ -514,3 +740,126 @@ pub fn gen_quadrants() -> Vec<BoardQuadrant> {
             .set_target((7, 5), Target::Spiral),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        all_standard_rounds, game_from_seed, gen_quadrants, quadrant_from_id,
+        round_and_start_from_seed, round_from_seed, Orientation, QuadColor, StandardBoardBuilder,
+        DISTINCT_STANDARD_ROUNDS,
+    };
+    use crate::{Direction, Position, Robot, ROBOTS};
+
+    #[test]
+    fn quadrant_to_board() {
+        let mut quad = gen_quadrants()[0].clone();
+        let board = quad.to_board();
+        assert_eq!(board.side_length(), 8);
+        assert!(board.is_adjacent_to_wall(Position::new(0, 5), Direction::Down));
+        assert!(board.is_adjacent_to_wall(Position::new(1, 0), Direction::Right));
+        assert!(!board.is_adjacent_to_wall(Position::new(1, 1), Direction::Right));
+        assert!(board.is_adjacent_to_wall(Position::new(7, 7), Direction::Up));
+        assert!(board.is_adjacent_to_wall(Position::new(7, 7), Direction::Left));
+
+        quad.rotate_to(Orientation::BottomRight);
+        let board = quad.to_board();
+        assert!(board.is_adjacent_to_wall(Position::new(0, 0), Direction::Down));
+        assert!(board.is_adjacent_to_wall(Position::new(0, 0), Direction::Right));
+        assert!(board.is_adjacent_to_wall(Position::new(7, 2), Direction::Up));
+    }
+
+    #[test]
+    fn all_rounds() {
+        let rounds = all_standard_rounds().collect::<Vec<_>>();
+        assert_eq!(rounds.len(), DISTINCT_STANDARD_ROUNDS);
+        for seed in [0, 16, 17, 1000, DISTINCT_STANDARD_ROUNDS - 1].iter() {
+            assert_eq!(rounds[*seed], round_from_seed(*seed));
+        }
+    }
+
+    #[test]
+    fn round_and_start() {
+        for start_seed in 0..50 {
+            let (round, start) = round_and_start_from_seed(100, start_seed);
+            assert_eq!(round, round_from_seed(100));
+            assert_eq!(
+                (round, start.clone()),
+                round_and_start_from_seed(100, start_seed)
+            );
+
+            let fields: Vec<Position> = ROBOTS.iter().map(|&robot| start[robot]).collect();
+            for (i, pos) in fields.iter().enumerate() {
+                assert!(!fields[i + 1..].contains(pos));
+                assert!(![7, 8].contains(&pos.column()) || ![7, 8].contains(&pos.row()));
+            }
+        }
+        assert_ne!(
+            round_and_start_from_seed(0, 0).1,
+            round_and_start_from_seed(0, 1).1
+        );
+    }
+
+    #[test]
+    fn robot_color_conversion() {
+        for &robot in ROBOTS.iter() {
+            assert_eq!(Robot::from(QuadColor::from(robot)), robot);
+        }
+        assert_eq!(Robot::from(QuadColor::Green), Robot::Green);
+    }
+
+    #[test]
+    fn mirror_twice() {
+        for quad in gen_quadrants() {
+            let mut mirrored = quad.clone();
+            mirrored.mirror();
+            assert_ne!(mirrored, quad);
+            mirrored.mirror();
+            assert_eq!(mirrored, quad);
+        }
+
+        let mut quad = gen_quadrants()[0].clone();
+        quad.rotate_to(Orientation::UpperRight);
+        let board = quad.to_board();
+        quad.mirror();
+        assert_eq!(quad.orientation(), Orientation::BottomLeft);
+        let mirrored = quad.to_board();
+        for col in 0..8 {
+            for row in 0..8 {
+                let pos = Position::new(col, row);
+                let transposed = Position::new(row, col);
+                assert_eq!(board[pos].right, mirrored[transposed].down);
+                assert_eq!(board[pos].down, mirrored[transposed].right);
+            }
+        }
+    }
+
+    #[test]
+    fn quadrant_ids() {
+        assert_eq!(quadrant_from_id("Red1"), Some(gen_quadrants()[0].clone()));
+        assert_eq!(quadrant_from_id("blue3"), Some(gen_quadrants()[5].clone()));
+        for id in ["Red0", "Red4", "Purple1", "Red", "1"].iter() {
+            assert_eq!(quadrant_from_id(id), None);
+        }
+    }
+
+    #[test]
+    fn build_standard_board() {
+        let builder = StandardBoardBuilder::new()
+            .upper_left("Red1")
+            .upper_right("Blue1")
+            .bottom_right("Green1");
+        assert!(builder.build().is_err());
+
+        let game = builder.clone().bottom_left("Yellow1").build().unwrap();
+        assert_eq!(game, game_from_seed(0));
+
+        assert_eq!(
+            builder.clone().bottom_left("Red2").build(),
+            Err("two quadrants have the same color")
+        );
+        assert_eq!(
+            builder.bottom_left("Yellow9").build(),
+            Err("unknown quadrant id")
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Rotating boards, rounds and positions by a quarter turn.
+//!
+//! All rotations are clockwise and map a field `column,row` on a board with side length `n` to
+//! `n - 1 - row,column`. Rotating the board together with the robots, the target and the directions
+//! of all moves keeps a solution valid, which can be used to map equivalent rounds onto each other.
+//! Boards can also be mirrored across their main diagonal, which maps `column,row` to `row,column`.
+
+use crate::{
+    Board, Direction, Field, Game, Position, PositionEncoding, RobotPositions, Round, Target,
+};
+
+impl Direction {
+    /// Returns the direction after rotating the board clockwise by a quarter turn.
+    pub fn rotate_clockwise(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// Returns the direction after rotating the board counterclockwise by a quarter turn.
+    pub fn rotate_counterclockwise(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    /// Returns the direction after mirroring the board across its main diagonal.
+    pub fn mirror(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Down,
+        }
+    }
+}
+
+impl Position {
+    /// Returns the position after rotating a board with `side_length` clockwise by a quarter turn.
+    pub fn rotate_clockwise(self, side_length: PositionEncoding) -> Self {
+        Position::new(side_length - 1 - self.row(), self.column())
+    }
+
+    /// Returns the position after mirroring a board across its main diagonal.
+    pub fn mirror(self) -> Self {
+        Position::new(self.row(), self.column())
+    }
+}
+
+impl RobotPositions {
+    /// Returns the positions after rotating a board with `side_length` clockwise by a quarter turn.
+    pub fn rotate_clockwise(&self, side_length: PositionEncoding) -> Self {
+        let [red, blue, green, yellow] = self.to_array();
+        let rotate = |pos: Position| pos.rotate_clockwise(side_length).into();
+        RobotPositions::from_tuples(&[rotate(red), rotate(blue), rotate(green), rotate(yellow)])
+    }
+
+    /// Returns the positions after mirroring a board across its main diagonal.
+    pub fn mirror(&self) -> Self {
+        let [red, blue, green, yellow] = self.to_array();
+        let mirror = |pos: Position| pos.mirror().into();
+        RobotPositions::from_tuples(&[mirror(red), mirror(blue), mirror(green), mirror(yellow)])
+    }
+}
+
+impl Board {
+    /// Returns the board rotated clockwise by a quarter turn.
+    ///
+    /// Walls and obstacles are rotated with the board. Moving a robot in the
+    /// [rotated direction](Direction::rotate_clockwise) on the rotated board stops it on the
+    /// rotated position of where it stops on this board.
+    pub fn rotate_clockwise(&self) -> Self {
+        let side_length = self.side_length();
+        let mut walls = vec![vec![Field::default(); side_length as usize]; side_length as usize];
+
+        for col in 0..side_length {
+            for row in 0..side_length {
+                let pos = Position::new(col, row);
+                let rotated = pos.rotate_clockwise(side_length);
+                // A wall to the right becomes a wall below the rotated field, a wall below becomes
+                // a wall to the left, which is stored on the field to the left of it.
+                if self[pos].right {
+                    walls[rotated.column() as usize][rotated.row() as usize].down = true;
+                }
+                if self[pos].down {
+                    let left = rotated.to_direction(Direction::Left, side_length);
+                    walls[left.column() as usize][left.row() as usize].right = true;
+                }
+            }
+        }
+
+        Board::new(walls).set_obstacles(
+            self.obstacles()
+                .iter()
+                .map(|pos| pos.rotate_clockwise(side_length))
+                .collect(),
+        )
+    }
+
+    /// Returns the board mirrored across its main diagonal.
+    ///
+    /// Walls to the right of a field become walls below the mirrored field and vice versa.
+    /// Moving a robot in the [mirrored direction](Direction::mirror) on the mirrored board stops it
+    /// on the mirrored position of where it stops on this board.
+    pub fn mirror(&self) -> Self {
+        let side_length = self.side_length();
+        Board::from_fn(side_length, |col, row| {
+            let field = self[Position::new(row, col)];
+            Field {
+                right: field.down,
+                down: field.right,
+            }
+        })
+        .set_obstacles(self.obstacles().iter().map(|pos| pos.mirror()).collect())
+    }
+
+    /// Returns all distinct boards created by rotating and mirroring this board.
+    ///
+    /// The four rotations of the board are followed by the four rotations of its
+    /// [mirror](Board::mirror), starting with the board itself. Variants equal to an earlier one
+    /// are left out, so a board with symmetries has fewer than eight variants.
+    pub fn symmetries(&self) -> Vec<Board> {
+        let mut variants: Vec<Board> = Vec::with_capacity(8);
+        let mut keys = Vec::with_capacity(8);
+        for start in [self.clone(), self.mirror()].iter() {
+            let mut board = start.clone();
+            for _ in 0..4 {
+                let mut obstacles = board.obstacles().to_vec();
+                obstacles.sort();
+                let key = (board.to_notation(), obstacles);
+                let rotated = board.rotate_clockwise();
+                if !keys.contains(&key) {
+                    keys.push(key);
+                    variants.push(board);
+                }
+                board = rotated;
+            }
+        }
+        variants
+    }
+}
+
+impl Round {
+    /// Returns the round with its board, target position and forbidden stops rotated clockwise by a
+    /// quarter turn.
+    ///
+    /// A custom [`condition`](Round::condition) is kept as it is, since it can't be rotated.
+    pub fn rotate_clockwise(&self) -> Self {
+        let side_length = self.board().side_length();
+        let round = Round::new(
+            self.board().rotate_clockwise(),
+            self.target(),
+            self.target_position().rotate_clockwise(side_length),
+        )
+        .with_forbidden_stops(
+            self.forbidden_stops()
+                .iter()
+                .map(|pos| pos.rotate_clockwise(side_length))
+                .collect(),
+            self.forbidden_stop_rule(),
+        );
+        match self.condition() {
+            Some(condition) => round.with_condition(condition.clone()),
+            None => round,
+        }
+    }
+}
+
+impl Game {
+    /// Returns the game with its board and all target positions rotated clockwise by a quarter
+    /// turn.
+    pub fn rotate_clockwise(&self) -> Self {
+        let side_length = self.board().side_length();
+        Game::new(
+            self.board().rotate_clockwise(),
+            self.iter_targets()
+                .map(|(target, pos)| (target, pos.rotate_clockwise(side_length)))
+                .collect(),
+        )
+    }
+
+    /// Rotates the game to its canonical orientation.
+    ///
+    /// Of the four rotations of the game the one with the smallest target positions, compared in
+    /// the order of the targets, is chosen. Ties are broken by the walls and obstacles. Games which
+    /// are rotations of each other are equal after being canonicalized, which makes it possible to
+    /// find duplicate boards.
+    pub fn canonicalize(&mut self) {
+        let mut rotated = self.clone();
+        let mut best_key = self.canonical_key();
+        for _ in 0..3 {
+            rotated = rotated.rotate_clockwise();
+            let key = rotated.canonical_key();
+            if key < best_key {
+                *self = rotated.clone();
+                best_key = key;
+            }
+        }
+    }
+
+    /// Returns the key compared by [`canonicalize`](Game::canonicalize) to pick a rotation.
+    fn canonical_key(&self) -> (Vec<(Target, Position)>, String, Vec<Position>) {
+        let mut obstacles = self.board().obstacles().to_vec();
+        obstacles.sort();
+        (
+            self.iter_targets().collect(),
+            self.board().to_notation(),
+            obstacles,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{quadrant, Board, Direction, Position, RobotPositions, DIRECTIONS, ROBOTS};
+
+    #[test]
+    fn rotate_position() {
+        assert_eq!(Position::new(0, 0).rotate_clockwise(4), Position::new(3, 0));
+        assert_eq!(Position::new(3, 0).rotate_clockwise(4), Position::new(3, 3));
+        assert_eq!(Position::new(1, 2).rotate_clockwise(4), Position::new(1, 1));
+    }
+
+    #[test]
+    fn rotate_direction() {
+        assert_eq!(Direction::Up.rotate_clockwise(), Direction::Right);
+        assert_eq!(Direction::Left.rotate_clockwise(), Direction::Up);
+        assert_eq!(Direction::Up.rotate_counterclockwise(), Direction::Left);
+        assert_eq!(Direction::Right.rotate_counterclockwise(), Direction::Up);
+
+        for &dir in DIRECTIONS.iter() {
+            assert_eq!(dir.rotate_clockwise().rotate_counterclockwise(), dir);
+            assert_eq!(dir.rotate_clockwise().rotate_clockwise(), dir.opposite());
+            assert_eq!(
+                dir.rotate_counterclockwise().rotate_counterclockwise(),
+                dir.opposite(),
+            );
+        }
+    }
+
+    #[test]
+    fn full_turn() {
+        let game = quadrant::game_from_seed(0);
+        let rotated = (0..4).fold(game.clone(), |game, _| game.rotate_clockwise());
+        assert_eq!(rotated, game);
+        assert_ne!(game.rotate_clockwise(), game);
+
+        for &dir in DIRECTIONS.iter() {
+            let rotated = (0..4).fold(dir, |dir, _| dir.rotate_clockwise());
+            assert_eq!(rotated, dir);
+        }
+    }
+
+    #[test]
+    fn canonical_orientation() {
+        let mut game = quadrant::game_from_seed(5);
+        game.canonicalize();
+        let canonical = game.clone();
+        game.canonicalize();
+        assert_eq!(game, canonical);
+
+        for _ in 0..3 {
+            game = game.rotate_clockwise();
+            let mut rotated = game.clone();
+            rotated.canonicalize();
+            assert_eq!(rotated, canonical);
+        }
+    }
+
+    #[test]
+    fn mirror_walls() {
+        let board = Board::new_empty(3)
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(1, 1, 1)
+            .set_obstacles(vec![Position::new(2, 0)]);
+        let expected = Board::new_empty(3)
+            .set_horizontal_line(0, 0, 1)
+            .set_vertical_line(1, 1, 1)
+            .set_obstacles(vec![Position::new(0, 2)]);
+        assert_eq!(board.mirror(), expected);
+        assert_eq!(board.mirror().mirror(), board);
+    }
+
+    #[test]
+    fn board_symmetries() {
+        let board = quadrant::game_from_seed(3).board().clone();
+        let symmetries = board.symmetries();
+        assert_eq!(symmetries.len(), 8);
+        assert_eq!(symmetries[0], board);
+        assert!(symmetries.contains(&board.mirror()));
+
+        let open = Board::new_empty(16).wall_enclosure();
+        assert_eq!(open.symmetries(), vec![open.clone()]);
+        let standard_center = open.set_center_walls().unwrap();
+        assert_eq!(standard_center.symmetries().len(), 1);
+
+        // A wall along the main diagonal is kept by mirroring.
+        let diagonal = Board::new_empty(4)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(0, 0, 1);
+        assert_eq!(diagonal.symmetries().len(), 4);
+    }
+
+    #[test]
+    fn rotate_walls() {
+        let board = Board::new_empty(3)
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(1, 1, 1)
+            .set_obstacles(vec![Position::new(2, 0)]);
+        let expected = Board::new_empty(3)
+            .set_horizontal_line(2, 0, 1)
+            .set_vertical_line(0, 1, 1)
+            .set_obstacles(vec![Position::new(2, 2)]);
+        assert_eq!(board.rotate_clockwise(), expected);
+    }
+
+    #[test]
+    fn moves_stay_consistent() {
+        let board = quadrant::game_from_seed(3).board().clone();
+        let rotated_board = board.rotate_clockwise();
+        let side_length = board.side_length();
+        let positions = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        let rotated_positions = positions.rotate_clockwise(side_length);
+        let mirrored_board = board.mirror();
+        let mirrored_positions = positions.mirror();
+
+        for &robot in ROBOTS.iter() {
+            for &dir in DIRECTIONS.iter() {
+                let moved = positions.clone().move_in_direction(&board, robot, dir);
+                let rotated_moved = rotated_positions.clone().move_in_direction(
+                    &rotated_board,
+                    robot,
+                    dir.rotate_clockwise(),
+                );
+                assert_eq!(moved.rotate_clockwise(side_length), rotated_moved);
+
+                let mirrored_moved = mirrored_positions.clone().move_in_direction(
+                    &mirrored_board,
+                    robot,
+                    dir.mirror(),
+                );
+                assert_eq!(moved.mirror(), mirrored_moved);
+            }
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Implementations of serde's `Serialize` and `Deserialize` for the enums naming robots, targets,
+//! symbols and directions as well as positions, boards and rounds, available with the `serde`
+//! feature.
+//!
+//! Robots, symbols and directions are represented by the names of their variants, e.g. `"Red"`,
+//! `"Triangle"` or `"Up"`. The spiral is represented as `"Spiral"` and all other targets as a map
+//! of their color and symbol, e.g. `{"color":"Red","symbol":"Triangle"}`.
+//!
+//! Positions are represented as `[column, row]`. A board is a map of its walls in the
+//! [notation](crate::Board::to_notation) used by many online solvers and its obstacles, which are
+//! left out if there are none. A round is a map of its board, target, target position and the
+//! fields robots are not allowed to stop on. A round with a custom
+//! [`TargetCondition`](crate::TargetCondition) can't be serialized and results in an error.
+
+use serde::de::Error;
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Board, ForbiddenStopRule, Position, PositionEncoding, Robot, Round, Symbol, Target};
+
+/// The representation of a [`Target`](Target) used for serialization.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum TargetRepr {
+    Spiral(SpiralRepr),
+    Colored { color: Robot, symbol: Symbol },
+}
+
+/// Serializes the spiral by the name of its only variant.
+#[derive(Serialize, Deserialize)]
+enum SpiralRepr {
+    Spiral,
+}
+
+impl From<Target> for TargetRepr {
+    fn from(target: Target) -> Self {
+        let (color, symbol) = match target {
+            Target::Red(symbol) => (Robot::Red, symbol),
+            Target::Blue(symbol) => (Robot::Blue, symbol),
+            Target::Green(symbol) => (Robot::Green, symbol),
+            Target::Yellow(symbol) => (Robot::Yellow, symbol),
+            Target::Spiral => return TargetRepr::Spiral(SpiralRepr::Spiral),
+        };
+        TargetRepr::Colored { color, symbol }
+    }
+}
+
+impl From<TargetRepr> for Target {
+    fn from(repr: TargetRepr) -> Self {
+        match repr {
+            TargetRepr::Spiral(SpiralRepr::Spiral) => Target::Spiral,
+            TargetRepr::Colored { color, symbol } => match color {
+                Robot::Red => Target::Red(symbol),
+                Robot::Blue => Target::Blue(symbol),
+                Robot::Green => Target::Green(symbol),
+                Robot::Yellow => Target::Yellow(symbol),
+            },
+        }
+    }
+}
+
+impl Serialize for Target {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TargetRepr::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Target {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TargetRepr::deserialize(deserializer).map(Target::from)
+    }
+}
+
+impl Serialize for Position {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.column(), self.row()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Position {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <(PositionEncoding, PositionEncoding)>::deserialize(deserializer).map(Position::from)
+    }
+}
+
+/// The representation of a [`Board`](Board) used for serialization.
+#[derive(Serialize, Deserialize)]
+struct BoardRepr {
+    walls: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    obstacles: Vec<Position>,
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BoardRepr {
+            walls: self.to_notation(),
+            obstacles: self.obstacles().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = BoardRepr::deserialize(deserializer)?;
+        let board = Board::from_notation(&repr.walls).map_err(D::Error::custom)?;
+        if !repr.obstacles.iter().all(|&pos| is_on_board(&board, pos)) {
+            return Err(D::Error::custom("An obstacle is outside of the board"));
+        }
+        Ok(board.set_obstacles(repr.obstacles))
+    }
+}
+
+/// The representation of a [`Round`](Round) used for serialization.
+#[derive(Serialize, Deserialize)]
+struct RoundRepr {
+    board: Board,
+    target: Target,
+    target_position: Position,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    forbidden_stops: Vec<Position>,
+    #[serde(default = "default_forbidden_stop_rule")]
+    forbidden_stop_rule: ForbiddenStopRule,
+}
+
+/// The rule used by [`Round::new`](Round::new).
+fn default_forbidden_stop_rule() -> ForbiddenStopRule {
+    ForbiddenStopRule::Disallowed
+}
+
+impl Serialize for Round {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.condition().is_some() {
+            return Err(S::Error::custom(
+                "A round with a custom condition can't be serialized",
+            ));
+        }
+        let mut forbidden_stops: Vec<Position> = self.forbidden_stops().iter().copied().collect();
+        forbidden_stops.sort();
+        RoundRepr {
+            board: self.board().clone(),
+            target: self.target(),
+            target_position: self.target_position(),
+            forbidden_stops,
+            forbidden_stop_rule: self.forbidden_stop_rule(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Round {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = RoundRepr::deserialize(deserializer)?;
+        if !is_on_board(&repr.board, repr.target_position) {
+            return Err(D::Error::custom(
+                "The target position is outside of the board",
+            ));
+        }
+        if !repr
+            .forbidden_stops
+            .iter()
+            .all(|&pos| is_on_board(&repr.board, pos))
+        {
+            return Err(D::Error::custom("A forbidden stop is outside of the board"));
+        }
+        Ok(
+            Round::new(repr.board, repr.target, repr.target_position).with_forbidden_stops(
+                repr.forbidden_stops.into_iter().collect(),
+                repr.forbidden_stop_rule,
+            ),
+        )
+    }
+}
+
+/// Checks if `pos` is within the bounds of `board`.
+fn is_on_board(board: &Board, pos: Position) -> bool {
+    pos.column() < board.side_length() && pos.row() < board.side_length()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use crate::{
+        quadrant, Direction, ForbiddenStopRule, Position, Robot, Round, StandardCondition, Symbol,
+        Target, DIRECTIONS, ROBOTS, TARGETS,
+    };
+
+    #[test]
+    fn format() {
+        assert_eq!(serde_json::to_string(&Robot::Green).unwrap(), r#""Green""#);
+        assert_eq!(
+            serde_json::to_string(&Symbol::Triangle).unwrap(),
+            r#""Triangle""#
+        );
+        assert_eq!(serde_json::to_string(&Direction::Up).unwrap(), r#""Up""#);
+        assert_eq!(
+            serde_json::to_string(&Target::Red(Symbol::Triangle)).unwrap(),
+            r#"{"color":"Red","symbol":"Triangle"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Target::Spiral).unwrap(),
+            r#""Spiral""#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for target in TARGETS.iter() {
+            let json = serde_json::to_string(target).unwrap();
+            assert_eq!(&serde_json::from_str::<Target>(&json).unwrap(), target);
+        }
+        for robot in ROBOTS.iter() {
+            let json = serde_json::to_string(robot).unwrap();
+            assert_eq!(&serde_json::from_str::<Robot>(&json).unwrap(), robot);
+        }
+        for dir in DIRECTIONS.iter() {
+            let json = serde_json::to_string(dir).unwrap();
+            assert_eq!(&serde_json::from_str::<Direction>(&json).unwrap(), dir);
+        }
+        for symbol in Symbol::all().iter() {
+            let json = serde_json::to_string(symbol).unwrap();
+            assert_eq!(&serde_json::from_str::<Symbol>(&json).unwrap(), symbol);
+        }
+    }
+
+    #[test]
+    fn invalid_target() {
+        assert!(serde_json::from_str::<Target>(r#""Circle""#).is_err());
+        assert!(serde_json::from_str::<Target>(r#"{"color":"Purple","symbol":"Circle"}"#).is_err());
+    }
+
+    #[test]
+    fn round_round_trip() {
+        let round = quadrant::round_from_seed(42).with_forbidden_stops(
+            HashSet::from([Position::new(3, 4), Position::new(0, 15)]),
+            ForbiddenStopRule::PassThrough,
+        );
+        let json = serde_json::to_string(&round).unwrap();
+        assert_eq!(serde_json::from_str::<Round>(&json).unwrap(), round);
+
+        let plain = quadrant::round_from_seed(7);
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("forbidden_stops"));
+        assert_eq!(serde_json::from_str::<Round>(&json).unwrap(), plain);
+
+        let custom = plain.with_condition(Arc::new(StandardCondition::new(
+            Target::Spiral,
+            Position::new(0, 0),
+        )));
+        assert!(serde_json::to_string(&custom).is_err());
+    }
+
+    #[test]
+    fn invalid_round() {
+        let outside = r#"{"board":{"walls":"2\n"},"target":"Spiral","target_position":[2,0]}"#;
+        assert!(serde_json::from_str::<Round>(outside).is_err());
+        let obstacle = r#"{"board":{"walls":"2\n","obstacles":[[0,5]]},"target":"Spiral","target_position":[1,0]}"#;
+        assert!(serde_json::from_str::<Round>(obstacle).is_err());
+        let valid = r#"{"board":{"walls":"2\n0,0 E"},"target":"Spiral","target_position":[1,0]}"#;
+        let round = serde_json::from_str::<Round>(valid).unwrap();
+        assert_eq!(round.target_position(), Position::new(1, 0));
+        assert!(round.board()[Position::new(0, 0)].right);
+    }
+}
@@ -0,0 +1,111 @@
+//! Names and glyphs used to display the robots.
+
+use crate::{Robot, Target};
+
+/// The English names of the robots in the order red, blue, green, yellow.
+pub(crate) const DEFAULT_NAMES: [&str; 4] = ["Red", "Blue", "Green", "Yellow"];
+
+/// The letters marking the robots on a drawn board in the order red, blue, green, yellow.
+pub(crate) const DEFAULT_GLYPHS: [char; 4] = ['R', 'B', 'G', 'Y'];
+
+/// Maps each robot to the name and glyph it is displayed with.
+///
+/// The default theme uses the English names of the colors, which are also used by the `Display`
+/// implementations of [`Robot`](Robot) and [`Target`](Target), and their first letters as glyphs.
+/// Front-ends can use a different theme to translate the names or to mark robots in a way that can
+/// be distinguished without seeing the colors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorTheme {
+    names: [String; 4],
+    glyphs: [char; 4],
+}
+
+impl ColorTheme {
+    /// Creates a theme from the `names` and `glyphs` of the robots.
+    ///
+    /// Both arrays are used in the order red, blue, green, yellow.
+    pub fn new(names: [String; 4], glyphs: [char; 4]) -> Self {
+        Self { names, glyphs }
+    }
+
+    /// Sets the `name` and `glyph` used for `robot`.
+    pub fn set_robot(mut self, robot: Robot, name: impl Into<String>, glyph: char) -> Self {
+        self.names[robot_index(robot)] = name.into();
+        self.glyphs[robot_index(robot)] = glyph;
+        self
+    }
+
+    /// Returns the name of `robot`.
+    pub fn name(&self, robot: Robot) -> &str {
+        &self.names[robot_index(robot)]
+    }
+
+    /// Returns the glyph marking `robot` on a drawn board.
+    pub fn glyph(&self, robot: Robot) -> char {
+        self.glyphs[robot_index(robot)]
+    }
+
+    /// Returns the name of `target`, i.e. the name of its color followed by its symbol.
+    ///
+    /// The spiral isn't associated with a robot and is always called `Spiral`.
+    pub fn target_name(&self, target: Target) -> String {
+        match target {
+            Target::Red(symbol) => format!("{} {:?}", self.name(Robot::Red), symbol),
+            Target::Blue(symbol) => format!("{} {:?}", self.name(Robot::Blue), symbol),
+            Target::Green(symbol) => format!("{} {:?}", self.name(Robot::Green), symbol),
+            Target::Yellow(symbol) => format!("{} {:?}", self.name(Robot::Yellow), symbol),
+            Target::Spiral => "Spiral".to_string(),
+        }
+    }
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self::new(
+            [
+                DEFAULT_NAMES[0].to_string(),
+                DEFAULT_NAMES[1].to_string(),
+                DEFAULT_NAMES[2].to_string(),
+                DEFAULT_NAMES[3].to_string(),
+            ],
+            DEFAULT_GLYPHS,
+        )
+    }
+}
+
+/// Returns the index of `robot` in [`ROBOTS`](crate::ROBOTS).
+pub(crate) fn robot_index(robot: Robot) -> usize {
+    match robot {
+        Robot::Red => 0,
+        Robot::Blue => 1,
+        Robot::Green => 2,
+        Robot::Yellow => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorTheme;
+    use crate::{Robot, Symbol, Target, TARGETS};
+
+    #[test]
+    fn default_matches_display() {
+        let theme = ColorTheme::default();
+        assert_eq!(theme.name(Robot::Green), Robot::Green.to_string());
+        assert_eq!(theme.glyph(Robot::Yellow), 'Y');
+        for &target in TARGETS.iter() {
+            assert_eq!(theme.target_name(target), target.to_string());
+        }
+    }
+
+    #[test]
+    fn custom_names() {
+        let theme = ColorTheme::default().set_robot(Robot::Yellow, "Gelb", 'Y');
+        assert_eq!(theme.name(Robot::Yellow), "Gelb");
+        assert_eq!(theme.name(Robot::Red), "Red");
+        assert_eq!(
+            theme.target_name(Target::Yellow(Symbol::Square)),
+            "Gelb Square"
+        );
+    }
+}
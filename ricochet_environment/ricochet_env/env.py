@@ -81,8 +81,9 @@ class RicochetEnv(gym.Env):
                         with walls below them. Followed by the robot positions
                         in order red, blue, green, yellow as `(column, row)`
                         tuples. The next two values are the position of the
-                        target and the final five values are the one hot encoded
-                        target type/color.
+                        target, followed by five values of the one hot encoded
+                        target type/color. The final value is a lower bound of
+                        the moves needed to reach the target.
             - "tensor": The observation is a tensor of the shape (board_size,
                         board_size, 11). These layers are right walls, down
                         walls, red robot, blue robot, green robot, yellow robot,
@@ -97,17 +98,18 @@ class RicochetEnv(gym.Env):
                 board_size, walls, targets, robots, seed
             )
 
-        self.action_space = spaces.Discrete(16)
+        self.action_space = spaces.Discrete(self.env.action_space())
         if observation == "vector":
             # right walls, down walls, 4 robot positions, 1 target position,
-            # and 5 one hot encoded target types
-            values = 2 * (board_size ** 2) + 8 + 2 + 5
+            # 5 one hot encoded target types, and the lower bound of moves
+            values = 2 * (board_size ** 2) + 8 + 2 + 5 + 1
             low_bounds = np.zeros(values)
             high_bounds = np.concatenate(
                 [
                     np.ones(2 * (board_size ** 2)),
                     np.full(8 + 2, board_size - 1),
                     np.ones(5),
+                    np.full(1, board_size ** 2),
                 ]
             )
             self.observation_space = spaces.Box(
@@ -142,8 +144,30 @@ class RicochetEnv(gym.Env):
     def board_size(self):
         return self.env.board_size
 
+    def optimal_length(self):
+        return self.env.optimal_length()
+
+    def set_reward_fn(self, reward_fn, reward_range=(-np.inf, np.inf)):
+        """Replaces the reward returned by `step`.
+
+        Parameters
+        ----------
+        reward_fn : Callable or None
+            Called with the observation after each step, has to return the
+            reward as a float. `None` restores the default reward of 1 for
+            reaching the target and 0 otherwise.
+        reward_range: Tuple(float, float)
+            The range of the rewards returned by `reward_fn`.
+        """
+        if reward_fn is None:
+            self.env.set_reward_fn(None)
+            self.reward_range = (0, 1)
+        else:
+            self.env.set_reward_fn(lambda obs: reward_fn(self._fit_observation(obs)))
+            self.reward_range = reward_range
+
     def _fit_observation(self, rust_obs):
-        right_walls, down_walls, robots, target_pos, target = rust_obs
+        right_walls, down_walls, robots, target_pos, target, min_moves = rust_obs
         right_walls = np.array(right_walls, dtype=int)
         down_walls = np.array(down_walls, dtype=int)
         if self.observation == "vector":
@@ -157,6 +181,7 @@ class RicochetEnv(gym.Env):
                     np.array(robots).flatten(),
                     target_pos,
                     target_one_hot,
+                    [min_moves],
                 ]
             )
         elif self.observation == "tensor":
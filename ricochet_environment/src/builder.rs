@@ -100,12 +100,10 @@ impl EnvironmentBuilder {
                 let (t, tp) = *targets.choose(&mut self.rng).expect("target list is empty");
                 (t.into(), tp.into())
             }
-            TargetConfig::Variants => game
-                .targets()
-                .iter()
+            TargetConfig::Variants => *game
+                .iter_targets()
                 .collect::<Vec<_>>()
                 .choose(&mut self.rng)
-                .map(|&(&t, &tp)| (t, tp))
                 .expect("could not get a target from a `Game`"),
         };
 
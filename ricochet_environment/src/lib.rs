@@ -1,18 +1,27 @@
 pub(crate) mod builder;
+mod vectorized;
 
 use crate::builder::{EnvironmentBuilder, RobotConfig, TargetConfig, WallConfig};
 use ndarray::Array2;
 use numpy::{PyArray2, ToPyArray};
 use getset::CopyGetters;
 use pyo3::prelude::*;
+use pyo3::{PyTraverseError, PyVisit};
 use ricochet_board::{
-    Board, Direction, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target,
+    Board, Direction, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target, DIRECTIONS,
+    ROBOTS,
 };
+use ricochet_solver::util::LeastMovesBoard;
+use ricochet_solver::{AStar, Solver};
+use std::cell::Cell;
+
+pub use crate::vectorized::VectorizedEnvironment;
 
 /// The base module of the created package.
 #[pymodule]
 fn ricochet_env(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<RustyEnvironment>()?;
+    m.add_class::<VectorizedEnvironment>()?;
 
     Ok(())
 }
@@ -31,14 +40,22 @@ pub type Coordinate = (PositionEncoding, PositionEncoding);
 /// - the positions of the robots in the order red, blue, green, yellow as (column, row) tuples
 /// - the position of the target
 /// - the color of the target
+/// - the lower bound of moves needed to reach the target from the current position
 pub type Observation<'a> = (
     &'a PyArray2<bool>,
     &'a PyArray2<bool>,
     Vec<Coordinate>,
     Coordinate,
     usize,
+    usize,
 );
 
+/// The description of a part of an [`Observation`](Observation).
+///
+/// The tuple consists of the shape of the part, which is empty for single values, and the inclusive
+/// lower and upper bound of its values.
+pub type SpaceSpec = (Vec<usize>, usize, usize);
+
 /// An action that can be performed in the environment.
 ///
 /// It consists of a robot and the direction the specified robot should move in.
@@ -66,9 +83,15 @@ pub struct RustyEnvironment {
     config: EnvironmentBuilder,
     round: Round,
     wall_observation: (Array2<bool>, Array2<bool>),
+    move_board: LeastMovesBoard,
     starting_position: RobotPositions,
     current_position: RobotPositions,
     steps_taken: usize,
+    /// The length of an optimal solution from the starting position, once it has been calculated.
+    optimal_length: Cell<Option<usize>>,
+    /// A python callable replacing the default reward, see
+    /// [`set_reward_fn`](RustyEnvironment::set_reward_fn).
+    reward_fn: Option<PyObject>,
 }
 
 #[pymethods]
@@ -106,10 +129,13 @@ impl RustyEnvironment {
 
         Self {
             wall_observation: create_wall_bitboards(round.board()),
+            move_board: LeastMovesBoard::new(round.board(), round.target_position()),
             round,
             current_position: starting_position.clone(),
             starting_position,
             steps_taken: 0,
+            optimal_length: Cell::new(None),
+            reward_fn: None,
             config,
         }
     }
@@ -120,31 +146,125 @@ impl RustyEnvironment {
         self.config.board_size()
     }
 
+    /// Returns the number of distinct actions, which are encoded as integers in `[0:16]`.
+    pub fn action_space(&self) -> usize {
+        ROBOTS.len() * DIRECTIONS.len()
+    }
+
+    /// Returns a description of each part of an observation in the same order as the observation.
+    ///
+    /// Every part is described by its shape and the inclusive bounds of its values, which is enough
+    /// to create a gym `Box` for it.
+    pub fn observation_space(&self) -> Vec<SpaceSpec> {
+        let size = self.board_size() as usize;
+        vec![
+            (vec![size, size], 0, 1),
+            (vec![size, size], 0, 1),
+            (vec![ROBOTS.len(), 2], 0, size - 1),
+            (vec![2], 0, size - 1),
+            (vec![], 0, 4),
+            (vec![], 0, size * size),
+        ]
+    }
+
     /// Performs an action to change the environment and returns a tuple (observation, reward, done).
-    pub fn step(&mut self, py_gil: Python, action: Action) -> PyObject {
+    ///
+    /// The reward is 1 if the target has been reached and 0 otherwise, unless a reward function has
+    /// been set with [`set_reward_fn`](RustyEnvironment::set_reward_fn).
+    pub fn step(&mut self, py_gil: Python, action: Action) -> PyResult<PyObject> {
+        let (mut reward, done) = self.apply_action(action);
+        let observation = self.observation(py_gil).to_object(py_gil);
+        if let Some(reward_fn) = &self.reward_fn {
+            reward = reward_fn
+                .call1(py_gil, (observation.clone_ref(py_gil),))?
+                .extract(py_gil)?;
+        }
+        Ok((observation, reward, done).to_object(py_gil))
+    }
+
+    /// Replaces the default reward with the result of calling `reward_fn` with the observation
+    /// after each step, or restores the default reward if `reward_fn` is `None`.
+    ///
+    /// `reward_fn` has to return a float. Errors raised by it are passed on by
+    /// [`step`](RustyEnvironment::step).
+    pub fn set_reward_fn(&mut self, reward_fn: Option<PyObject>) {
+        self.reward_fn = reward_fn;
+    }
+
+    /// Lets the garbage collector of python visit the reward function.
+    ///
+    /// The reward function may hold a reference to the python object owning this environment, the
+    /// resulting reference cycle can only be collected if the garbage collector knows about it.
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        if let Some(reward_fn) = &self.reward_fn {
+            visit.call(reward_fn)?;
+        }
+        Ok(())
+    }
+
+    /// Drops the reward function to break a reference cycle found by the garbage collector.
+    fn __clear__(&mut self) {
+        self.reward_fn = None;
+    }
+
+    /// Resets the environment which means a new state is created according to the configuration.
+    pub fn reset(&mut self, py_gil: Python) -> PyObject {
+        self.new_round();
+        self.get_state(py_gil)
+    }
+
+    /// Returns a simple drawing of the walls with unicode box drawing characters.
+    pub fn render(&self) -> String {
+        ricochet_board::draw_board(self.round.board().get_walls())
+    }
+
+    /// Get the current state of the environment.
+    pub fn get_state(&self, py_gil: Python) -> PyObject {
+        self.observation(py_gil).to_object(py_gil)
+    }
+
+    /// Returns the number of moves in an optimal solution of the current round from the starting
+    /// position.
+    ///
+    /// The round is solved on the first call after a reset, further calls return the cached length.
+    pub fn optimal_length(&self) -> usize {
+        match self.optimal_length.get() {
+            Some(length) => length,
+            None => {
+                let length = AStar::new()
+                    .solve(&self.round, self.starting_position.clone())
+                    .len();
+                self.optimal_length.set(Some(length));
+                length
+            }
+        }
+    }
+}
+
+impl RustyEnvironment {
+    /// Moves a robot according to `action` and returns the reward and whether the target has been
+    /// reached.
+    fn apply_action(&mut self, action: Action) -> (Reward, bool) {
         self.current_position = self.current_position.clone().move_in_direction(
             self.round.board(),
             action.robot,
             action.direction,
         );
 
-        let mut reward = 0.0;
-        let mut done = false;
         if self.round.target_reached(&self.current_position) {
-            reward = 1.0;
-            done = true;
+            (1.0, true)
+        } else {
+            (0.0, false)
         }
-
-        let output = (self.observation(py_gil), reward, done);
-        output.to_object(py_gil)
     }
 
-    /// Resets the environment which means a new state is created according to the configuration.
-    pub fn reset(&mut self, py_gil: Python) -> PyObject {
+    /// Creates a new round and starting position according to the configuration.
+    fn new_round(&mut self) {
         self.round = self.config.new_round();
         if *self.config.walls() != WallConfig::Fix {
             self.wall_observation = create_wall_bitboards(self.round.board());
         }
+        self.move_board = LeastMovesBoard::new(self.round.board(), self.round.target_position());
         self.starting_position = loop {
             let pos = self.config.new_positions();
             if !self.round.target_reached(&pos) {
@@ -153,40 +273,27 @@ impl RustyEnvironment {
         };
         self.current_position = self.starting_position.clone();
         self.steps_taken = 0;
-
-        self.get_state(py_gil)
-    }
-
-    /// Returns a simple drawing of the walls with unicode box drawing characters.
-    pub fn render(&self) -> String {
-        ricochet_board::draw_board(self.round.board().get_walls())
-    }
-
-    /// Get the current state of the environment.
-    pub fn get_state(&self, py_gil: Python) -> PyObject {
-        self.observation(py_gil).to_object(py_gil)
+        self.optimal_length.set(None);
     }
-}
 
-impl RustyEnvironment {
     /// Creates an observation from the current state of the environment.
     fn observation<'a>(&self, py_gil: Python<'a>) -> Observation<'a> {
         let target_pos = self.round.target_position();
-        let target = match self.round.target() {
-            Target::Red(_) => 0,
-            Target::Blue(_) => 1,
-            Target::Green(_) => 2,
-            Target::Yellow(_) => 3,
-            Target::Spiral => 4,
-        };
         (
             self.wall_observation.0.view().to_pyarray(py_gil),
             self.wall_observation.1.view().to_pyarray(py_gil),
             robot_positions_as_vec(&self.current_position),
             (target_pos.column(), target_pos.row()),
-            target,
+            self.round.target_color_index(),
+            self.min_moves(),
         )
     }
+
+    /// Returns the lower bound of moves needed to reach the target from the current position.
+    fn min_moves(&self) -> usize {
+        self.move_board
+            .min_moves(&self.current_position, self.round.target())
+    }
 }
 
 impl Action {
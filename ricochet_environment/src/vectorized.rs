@@ -0,0 +1,179 @@
+use ndarray::{stack, Array3, Axis};
+use numpy::{PyArray3, ToPyArray};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use ricochet_board::PositionEncoding;
+
+use crate::builder::{RobotConfig, TargetConfig, WallConfig};
+use crate::{robot_positions_as_vec, Action, Coordinate, Reward, RustyEnvironment};
+
+/// The observations of all environments in a [`VectorizedEnvironment`](VectorizedEnvironment).
+///
+/// The tuple contains the same parts as an [`Observation`](crate::Observation), but each part
+/// holds the values of all environments. The wall bitboards are stacked along the first axis, all
+/// other parts are lists with one entry per environment.
+pub type StackedObservation<'a> = (
+    &'a PyArray3<bool>,
+    &'a PyArray3<bool>,
+    Vec<Vec<Coordinate>>,
+    Vec<Coordinate>,
+    Vec<usize>,
+    Vec<usize>,
+);
+
+/// Multiple independent environments with the same configuration which are stepped together.
+///
+/// Stepping all environments with a single call avoids crossing the boundary between python and
+/// rust once per environment.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct VectorizedEnvironment {
+    envs: Vec<RustyEnvironment>,
+}
+
+#[pymethods]
+impl VectorizedEnvironment {
+    /// Creates `count` environments with the given configuration.
+    #[new]
+    pub fn new(
+        count: usize,
+        board_size: PositionEncoding,
+        walls: WallConfig,
+        targets: TargetConfig,
+        robots: RobotConfig,
+    ) -> Self {
+        Self::new_seeded(count, board_size, walls, targets, robots, rand::random())
+    }
+
+    /// Creates `count` environments with the given configuration and seed to make them
+    /// reproducible.
+    ///
+    /// The environments are seeded with `seed`, `seed + 1`, ... so they create different rounds.
+    #[staticmethod]
+    pub fn new_seeded(
+        count: usize,
+        board_size: PositionEncoding,
+        walls: WallConfig,
+        targets: TargetConfig,
+        robots: RobotConfig,
+        seed: u128,
+    ) -> Self {
+        let envs = (0..count)
+            .map(|i| {
+                RustyEnvironment::new_seeded(
+                    board_size,
+                    walls.clone(),
+                    targets.clone(),
+                    robots.clone(),
+                    seed.wrapping_add(i as u128),
+                )
+            })
+            .collect();
+        Self { envs }
+    }
+
+    /// Returns the number of environments.
+    pub fn __len__(&self) -> usize {
+        self.envs.len()
+    }
+
+    /// Returns the side length of the boards.
+    #[getter]
+    pub fn board_size(&self) -> PositionEncoding {
+        self.envs.first().map_or(0, |env| env.board_size())
+    }
+
+    /// Performs one action in each environment and returns a tuple (observations, rewards, dones).
+    ///
+    /// The i-th action is performed in the i-th environment. Environments that reached their
+    /// target are reset automatically, so their observation is the one of the new round.
+    pub fn step(&mut self, py_gil: Python, actions: Vec<Action>) -> PyResult<PyObject> {
+        if actions.len() != self.envs.len() {
+            return Err(PyValueError::new_err(format!(
+                "expected {} actions, one for each environment, but got {}",
+                self.envs.len(),
+                actions.len()
+            )));
+        }
+
+        let mut rewards = Vec::with_capacity(self.envs.len());
+        let mut dones = Vec::with_capacity(self.envs.len());
+        for (env, action) in self.envs.iter_mut().zip(actions) {
+            let (reward, done) = env.apply_action(action);
+            if done {
+                env.new_round();
+            }
+            rewards.push(reward);
+            dones.push(done);
+        }
+
+        let output: (_, Vec<Reward>, Vec<bool>) = (self.observation(py_gil), rewards, dones);
+        Ok(output.to_object(py_gil))
+    }
+
+    /// Resets all environments and returns their stacked observations.
+    pub fn reset(&mut self, py_gil: Python) -> PyObject {
+        for env in &mut self.envs {
+            env.new_round();
+        }
+        self.get_state(py_gil)
+    }
+
+    /// Get the current state of all environments.
+    pub fn get_state(&self, py_gil: Python) -> PyObject {
+        self.observation(py_gil).to_object(py_gil)
+    }
+}
+
+impl VectorizedEnvironment {
+    /// Creates the stacked observations of all environments.
+    fn observation<'a>(&self, py_gil: Python<'a>) -> StackedObservation<'a> {
+        let (right_walls, down_walls) = self.stacked_walls();
+        (
+            right_walls.to_pyarray(py_gil),
+            down_walls.to_pyarray(py_gil),
+            self.envs
+                .iter()
+                .map(|env| robot_positions_as_vec(&env.current_position))
+                .collect(),
+            self.envs
+                .iter()
+                .map(|env| {
+                    let target_pos = env.round.target_position();
+                    (target_pos.column(), target_pos.row())
+                })
+                .collect(),
+            self.envs
+                .iter()
+                .map(|env| env.round.target_color_index())
+                .collect(),
+            self.envs.iter().map(|env| env.min_moves()).collect(),
+        )
+    }
+
+    /// Stacks the wall bitboards of all environments along a new first axis.
+    fn stacked_walls(&self) -> (Array3<bool>, Array3<bool>) {
+        let size = self.board_size() as usize;
+        if self.envs.is_empty() {
+            return (
+                Array3::from_elem((0, size, size), false),
+                Array3::from_elem((0, size, size), false),
+            );
+        }
+
+        let right: Vec<_> = self
+            .envs
+            .iter()
+            .map(|env| env.wall_observation.0.view())
+            .collect();
+        let down: Vec<_> = self
+            .envs
+            .iter()
+            .map(|env| env.wall_observation.1.view())
+            .collect();
+        (
+            stack(Axis(0), &right).expect("all boards have the same size"),
+            stack(Axis(0), &down).expect("all boards have the same size"),
+        )
+    }
+}
@@ -13,12 +13,22 @@ fn bench_solvers(c: &mut Criterion) {
         group.bench_function(BenchmarkId::new("Breadth-First", moves), |b| {
             b.iter(|| BreadthFirst::new().solve(&round, pos.clone()))
         });
+        group.bench_function(BenchmarkId::new("Breadth-First best first", moves), |b| {
+            b.iter(|| {
+                BreadthFirst::new()
+                    .best_first_within_depth(true)
+                    .solve(&round, pos.clone())
+            })
+        });
         group.bench_function(BenchmarkId::new("IDA*", moves), |b| {
             b.iter(|| IdaStar::new().solve(&round, pos.clone()))
         });
         group.bench_function(BenchmarkId::new("A*", moves), |b| {
             b.iter(|| AStar::new().solve(&round, pos.clone()))
         });
+        group.bench_function(BenchmarkId::new("A* with move table", moves), |b| {
+            b.iter(|| AStar::with_move_table().solve(&round, pos.clone()))
+        });
     }
     group.finish();
 }
@@ -31,7 +41,35 @@ fn bench_util(c: &mut Criterion) {
     group.bench_function(BenchmarkId::new("LeastMovesBoard", ""), |b| {
         b.iter(|| LeastMovesBoard::new(game.board(), target_position))
     });
+    group.bench_function(BenchmarkId::new("MoveTable", ""), |b| {
+        b.iter(|| game.board().build_move_table())
+    });
+
+    let move_table = game.board().build_move_table();
+    group.bench_function(BenchmarkId::new("reachable_positions", ""), |b| {
+        b.iter(|| pos.reachable_positions(game.board()).count())
+    });
+    group.bench_function(
+        BenchmarkId::new("reachable_positions_with_table", ""),
+        |b| b.iter(|| pos.reachable_positions_with_table(&move_table).count()),
+    );
+
+    group.finish();
+}
+
+fn bench_round_generation(c: &mut Criterion) {
+    let pos = RobotPositions::from_tuples(&[(15, 15), (15, 0), (0, 15), (0, 0)]);
+    let seeds = (0..quadrant::DISTINCT_STANDARD_ROUNDS).step_by(1187);
 
+    let mut group = c.benchmark_group("Round generation");
+    for seed in seeds {
+        group.bench_function(BenchmarkId::new("round_from_seed", seed), |b| {
+            b.iter(|| quadrant::round_from_seed(seed))
+        });
+        group.bench_function(BenchmarkId::new("round_from_seed + A*", seed), |b| {
+            b.iter(|| AStar::new().solve(&quadrant::round_from_seed(seed), pos.clone()))
+        });
+    }
     group.finish();
 }
 
@@ -54,7 +92,13 @@ fn bench_22_move_problem(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_solvers, bench_util, bench_22_move_problem);
+criterion_group!(
+    benches,
+    bench_solvers,
+    bench_util,
+    bench_round_generation,
+    bench_22_move_problem
+);
 criterion_main!(benches);
 
 fn solver_bench_setup() -> (RobotPositions, Vec<(Round, usize)>) {
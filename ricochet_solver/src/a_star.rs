@@ -1,11 +1,14 @@
 use fxhash::FxBuildHasher;
 use priority_queue::PriorityQueue;
-use ricochet_board::{RobotPositions, Round};
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
 use std::cmp::Reverse;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
 use std::usize;
 
 use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNodes};
-use crate::{Path, Solver};
+use crate::{is_cancelled, start_is_solution, Path, SolveError, SolveStats, Solver};
 
 /// A solver using the [A*](https://en.wikipedia.org/wiki/A*_search_algorithm) search algorithm to
 /// find a path to the target.
@@ -16,6 +19,10 @@ use crate::{Path, Solver};
 pub struct AStar {
     visited_nodes: VisitedNodes<BasicVisitedNode>,
     move_board: LeastMovesBoard,
+    stats: SolveStats,
+    /// Whether robots are moved with a [`MoveTable`](ricochet_board::MoveTable) built for each
+    /// solved round.
+    use_move_table: bool,
 }
 
 impl AStar {
@@ -24,29 +31,50 @@ impl AStar {
         Self {
             visited_nodes: VisitedNodes::with_capacity(65536),
             move_board: Default::default(),
+            stats: Default::default(),
+            use_move_table: false,
         }
     }
-}
 
-impl Solver for AStar {
-    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
-        // Check if the target has already been reached.
-        if round.target_reached(&start_positions) {
-            return Path::new_start_on_target(start_positions);
+    /// Creates a new `AStar` solver which moves the robots with a
+    /// [`MoveTable`](ricochet_board::MoveTable).
+    ///
+    /// The table is built at the start of each solve, which pays off for solutions of about six
+    /// moves or more. Rounds with [`forbidden_stops`](Round::forbidden_stops) are solved without
+    /// the table.
+    pub fn with_move_table() -> Self {
+        Self {
+            use_move_table: true,
+            ..Self::new()
+        }
+    }
+
+    /// Searches for a path like [`solve_cancellable`](Solver::solve_cancellable) without measuring
+    /// the elapsed time.
+    fn find_path(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Path, SolveError> {
+        self.stats = Default::default();
+
+        if let Some(path) = start_is_solution(round, &start_positions) {
+            return Ok(path);
         }
 
         // Check if the problem may be impossible to solve.
         self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
         if self
             .move_board
-            .is_unsolvable(&start_positions, round.target())
+            .is_impossible_bound(round.lower_bound(&self.move_board, &start_positions))
         {
             panic!("It's not possible to reach the target starting from this robot configuration");
         }
 
         // Use the least moves board as an admissable heuristic (never overestimates the moves needed).
         let move_board_ref = &self.move_board;
-        let moves_to_target = |pos: &RobotPositions| move_board_ref.min_moves(pos, round.target());
+        let moves_to_target = |pos: &RobotPositions| round.lower_bound(move_board_ref, pos);
 
         // Create a queue holding the not yet expanded nodes.
         let mut open_list =
@@ -61,6 +89,13 @@ impl Solver for AStar {
             MoveCounter::new(0, moves_to_target(&start_positions)),
         );
 
+        // The move table doesn't know about forbidden stops, so it can only be used without them.
+        let move_table = if self.use_move_table && round.forbidden_stops().is_empty() {
+            Some(round.board().build_move_table())
+        } else {
+            None
+        };
+
         let mut found_minimum = usize::MAX;
         let mut found_final_position = start_positions;
 
@@ -70,8 +105,25 @@ impl Solver for AStar {
                 // The shortest path has been found.
                 break;
             }
+            if is_cancelled(cancel) {
+                return Err(SolveError::Cancelled);
+            }
+            self.stats.expand(prio.from_start());
+
+            let (mut with_table, mut without_table);
+            let children: &mut dyn Iterator<Item = (RobotPositions, (Robot, Direction))> =
+                match &move_table {
+                    Some(table) => {
+                        with_table = from_pos.reachable_positions_with_table(table);
+                        &mut with_table
+                    }
+                    None => {
+                        without_table = round.reachable_positions(&from_pos);
+                        &mut without_table
+                    }
+                };
 
-            for (pos, movement) in from_pos.reachable_positions(round.board()) {
+            for (pos, movement) in children {
                 let moves_from_start = prio.from_start() + 1;
                 let moves_to_target = moves_to_target(&pos);
 
@@ -103,7 +155,34 @@ impl Solver for AStar {
             }
         }
 
-        self.visited_nodes.path_to(&found_final_position)
+        Ok(self.visited_nodes.path_to(&found_final_position))
+    }
+}
+
+impl Solver for AStar {
+    fn solve_cancellable(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Path, SolveError> {
+        let started = Instant::now();
+        let result = self.find_path(round, start_positions, cancel.as_deref());
+        self.stats.elapsed = started.elapsed();
+        result
+    }
+
+    fn last_stats(&self) -> SolveStats {
+        self.stats
+    }
+
+    fn name(&self) -> &'static str {
+        "a-star"
+    }
+
+    fn reset(&mut self) {
+        self.visited_nodes.clear();
+        self.stats = Default::default();
     }
 }
 
@@ -152,25 +231,15 @@ impl MoveCounter {
 #[cfg(test)]
 mod tests {
     use priority_queue::PriorityQueue;
-    use ricochet_board::{quadrant, Direction, Game, Robot, RobotPositions, Round, Symbol, Target};
+    use ricochet_board::{
+        Direction, Position, Robot, RobotPositions, Round, Symbol, Target, TargetCondition, ROBOTS,
+    };
+    use std::ops::Index;
+    use std::sync::Arc;
 
     use super::{AStar, MoveCounter, Path, Solver};
-
-    fn create_board() -> (RobotPositions, Game) {
-        let quadrants = quadrant::gen_quadrants()
-            .iter()
-            .step_by(3)
-            .cloned()
-            .enumerate()
-            .map(|(i, mut quad)| {
-                quad.rotate_to(quadrant::ORIENTATIONS[i]);
-                quad
-            })
-            .collect::<Vec<quadrant::BoardQuadrant>>();
-
-        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
-        (pos, Game::from_quadrants(&quadrants))
-    }
+    use crate::tests::{create_board, create_round};
+    use crate::BreadthFirst;
 
     #[test]
     fn board_creation() {
@@ -226,14 +295,7 @@ mod tests {
     // Test short path
     #[test]
     fn solve() {
-        let (pos, game) = create_board();
-        let target = Target::Yellow(Symbol::Hexagon);
-
-        let round = Round::new(
-            game.board().clone(),
-            target,
-            game.get_target_position(&target).unwrap(),
-        );
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
 
         let expected = Path::new(
             pos.clone(),
@@ -253,4 +315,38 @@ mod tests {
 
         assert_eq!(AStar::new().solve(&round, pos), expected);
     }
+
+    #[test]
+    fn custom_condition() {
+        /// Any robot may reach the target, like the spiral target.
+        struct AnyRobot(Position);
+        impl TargetCondition for AnyRobot {
+            fn is_satisfied(&self, positions: &RobotPositions) -> bool {
+                positions.contains_any_robot(self.0)
+            }
+
+            fn lower_bound(
+                &self,
+                least_moves: &dyn Index<Position, Output = usize>,
+                positions: &RobotPositions,
+            ) -> usize {
+                ROBOTS
+                    .iter()
+                    .map(|&robot| least_moves[positions[robot]])
+                    .min()
+                    .unwrap()
+            }
+        }
+
+        let (pos, game) = create_board();
+        let target = Target::Red(Symbol::Square);
+        let target_position = game.get_target_position(&target).unwrap();
+        let round = Round::new(game.board().clone(), target, target_position)
+            .with_condition(Arc::new(AnyRobot(target_position)));
+
+        let path = AStar::new().solve(&round, pos.clone());
+        assert!(path.verify(&round));
+        assert_eq!(path.len(), 3);
+        assert_eq!(path.len(), BreadthFirst::new().solve(&round, pos).len());
+    }
 }
@@ -1,7 +1,10 @@
 use ricochet_board::{RobotPositions, Round};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
 
-use crate::util::{BasicVisitedNode, VisitedNodes};
-use crate::{Path, Solver};
+use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNodes};
+use crate::{is_cancelled, start_is_solution, Path, SolveError, SolveStats, Solver};
 
 /// Finds an optimal solution by visiting all possible game states in order of moves needed to
 /// reach them.
@@ -9,16 +12,52 @@ use crate::{Path, Solver};
 pub struct BreadthFirst {
     /// Manages knowledge of visited nodes.
     visited_nodes: VisitedNodes<BasicVisitedNode>,
+    /// Statistics of the last search.
+    stats: SolveStats,
+    /// The size of the search at each depth of the last search, only recorded if enabled with
+    /// [`with_frontier_stats`](BreadthFirst::with_frontier_stats).
+    frontier_stats: Option<Vec<FrontierStats>>,
+    /// Whether the positions of each depth are sorted by their distance to the target.
+    best_first_within_depth: bool,
+}
+
+/// The size of a breadth first search after expanding all positions reached with the same number
+/// of moves.
+///
+/// Useful to understand the memory used by the search, since the positions of the next depth and
+/// the visited positions grow quickly with each move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrontierStats {
+    /// The number of positions stored to be expanded in the current and the next depth.
+    pub frontier: usize,
+    /// The number of positions visited so far.
+    pub visited: usize,
 }
 
 impl Solver for BreadthFirst {
-    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
-        // Check if the robot has already reached the target
-        if round.target_reached(&start_positions) {
-            return Path::new(start_positions.clone(), start_positions, vec![]);
-        }
+    fn solve_cancellable(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Path, SolveError> {
+        let started = Instant::now();
+        let result = self.find_path(round, start_positions, cancel.as_deref());
+        self.stats.elapsed = started.elapsed();
+        result
+    }
+
+    fn last_stats(&self) -> SolveStats {
+        self.stats
+    }
 
-        self.start(round, start_positions)
+    fn name(&self) -> &'static str {
+        "breadth-first"
+    }
+
+    fn reset(&mut self) {
+        self.visited_nodes.clear();
+        self.stats = Default::default();
     }
 }
 
@@ -27,10 +66,47 @@ impl BreadthFirst {
     pub fn new() -> Self {
         Self {
             visited_nodes: VisitedNodes::with_capacity(65536),
+            stats: Default::default(),
+            frontier_stats: None,
+            best_first_within_depth: false,
+        }
+    }
+
+    /// Sets whether the positions reached with the same number of moves are expanded in order of
+    /// their [`lower_bound`](Round::lower_bound) instead of the order they were found in.
+    ///
+    /// All positions of a depth are still expanded before the next depth, so the found solutions
+    /// stay optimal. Expanding the positions closest to the target first reaches it earlier in the
+    /// last depth, but sorting each depth takes time as well. Disabled by default.
+    pub fn best_first_within_depth(mut self, enabled: bool) -> Self {
+        self.best_first_within_depth = enabled;
+        self
+    }
+
+    /// Creates a new solver which records the [`FrontierStats`](FrontierStats) of each depth.
+    pub fn with_frontier_stats() -> Self {
+        Self {
+            frontier_stats: Some(Vec::new()),
+            ..Self::new()
         }
     }
 
-    fn start(&mut self, round: &Round, start_pos: RobotPositions) -> Path {
+    /// Returns the [`FrontierStats`](FrontierStats) of each depth of the last search, starting with
+    /// the expansion of the starting position.
+    ///
+    /// The last entry is recorded when the target is reached, so its depth may not have been fully
+    /// expanded. Returns `None` if the solver hasn't been created with
+    /// [`with_frontier_stats`](BreadthFirst::with_frontier_stats).
+    pub fn frontier_stats(&self) -> Option<&[FrontierStats]> {
+        self.frontier_stats.as_deref()
+    }
+
+    fn start(
+        &mut self,
+        round: &Round,
+        start_pos: RobotPositions,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Path, SolveError> {
         // contains all positions from which the positions in
         let mut current_move_positions: Vec<RobotPositions> = Vec::with_capacity(16usize.pow(3));
         current_move_positions.push(start_pos.clone());
@@ -38,24 +114,48 @@ impl BreadthFirst {
 
         // Initialize the positions which will store the final position.
         let mut final_pos = start_pos;
+        let move_board = if self.best_first_within_depth {
+            Some(LeastMovesBoard::new(round.board(), round.target_position()))
+        } else {
+            None
+        };
 
         // Forward pathing to the target.
         // Computes the min. number of moves to the target and creates a tree of reachable positions
         // in `visited_nodes`, which is later used in the path creation.
         'outer: for move_n in 0.. {
+            if let Some(move_board) = &move_board {
+                current_move_positions.sort_by_cached_key(|pos| round.lower_bound(move_board, pos));
+            }
             for pos in &current_move_positions {
+                if is_cancelled(cancel) {
+                    return Err(SolveError::Cancelled);
+                }
+                self.stats.expand(move_n);
                 if let Some(reached) =
                     self.eval_robot_state(round, pos, move_n, &mut next_move_positions)
                 {
+                    self.record_frontier(&current_move_positions, &next_move_positions);
                     final_pos = reached;
                     break 'outer;
                 };
             }
+            self.record_frontier(&current_move_positions, &next_move_positions);
             current_move_positions.clear();
             std::mem::swap(&mut current_move_positions, &mut next_move_positions)
         }
 
-        self.visited_nodes.path_to(&final_pos)
+        Ok(self.visited_nodes.path_to(&final_pos))
+    }
+
+    /// Records the size of the search if [`FrontierStats`](FrontierStats) are enabled.
+    fn record_frontier(&mut self, current: &[RobotPositions], next: &[RobotPositions]) {
+        if let Some(frontier_stats) = &mut self.frontier_stats {
+            frontier_stats.push(FrontierStats {
+                frontier: current.len() + next.len(),
+                visited: self.visited_nodes.len(),
+            });
+        }
     }
 
     /// Calculates all unseen reachable positions starting from `initial_pos` and adds them to
@@ -70,7 +170,7 @@ impl BreadthFirst {
         moves: usize,
         next_positions: &mut Vec<RobotPositions>,
     ) -> Option<RobotPositions> {
-        for (new_pos, (robot, dir)) in initial_pos.reachable_positions(round.board()) {
+        for (new_pos, (robot, dir)) in round.reachable_positions(initial_pos) {
             // Mark the new positions as visited and continue with the next one, if a better path
             // already exists.
             if self
@@ -98,6 +198,26 @@ impl BreadthFirst {
 
         None
     }
+
+    /// Searches for a path like [`solve_cancellable`](Solver::solve_cancellable) without measuring
+    /// the elapsed time.
+    fn find_path(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Path, SolveError> {
+        self.stats = Default::default();
+        if let Some(frontier_stats) = &mut self.frontier_stats {
+            frontier_stats.clear();
+        }
+
+        if let Some(path) = start_is_solution(round, &start_positions) {
+            return Ok(path);
+        }
+
+        self.start(round, start_positions, cancel)
+    }
 }
 
 impl Default for BreadthFirst {
@@ -109,6 +229,7 @@ impl Default for BreadthFirst {
 #[cfg(test)]
 mod tests {
     use super::BreadthFirst;
+    use crate::tests::{create_board, create_round, round_on};
     use crate::{Path, Solver};
     use chrono::prelude::*;
     use itertools::Itertools;
@@ -116,25 +237,10 @@ mod tests {
     use rand::SeedableRng;
     use rayon::prelude::*;
     use ricochet_board::*;
+    use std::collections::HashSet;
     use std::convert::TryInto;
     use std::{fmt, vec};
 
-    fn create_board() -> (RobotPositions, Game) {
-        let quadrants = quadrant::gen_quadrants()
-            .iter()
-            .step_by(3)
-            .cloned()
-            .enumerate()
-            .map(|(i, mut quad)| {
-                quad.rotate_to(quadrant::ORIENTATIONS[i]);
-                quad
-            })
-            .collect::<Vec<quadrant::BoardQuadrant>>();
-
-        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
-        (pos, Game::from_quadrants(&quadrants))
-    }
-
     #[test]
     fn board_creation() {
         create_board();
@@ -156,17 +262,27 @@ mod tests {
         assert_eq!(BreadthFirst::new().solve(&round, start), expected);
     }
 
+    // Test a small board drawn as ascii
+    #[test]
+    fn drawn_round() {
+        let (round, start) = round!(
+            Target::Red(Symbol::Square),
+            "
+            R . . .
+            . . . .
+            . . . *
+            B G . Y
+            "
+        );
+        let path = BreadthFirst::new().solve(&round, start);
+        assert!(path.verify(&round));
+        assert_eq!((path.len(), path.robot_count()), (2, 1));
+    }
+
     // Test short path
     #[test]
     fn solve() {
-        let (pos, game) = create_board();
-        let target = Target::Yellow(Symbol::Hexagon);
-
-        let round = Round::new(
-            game.board().clone(),
-            target,
-            game.get_target_position(&target).unwrap(),
-        );
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
 
         let expected = Path::new(
             pos.clone(),
@@ -187,12 +303,77 @@ mod tests {
         assert_eq!(BreadthFirst::new().solve(&round, pos), expected);
     }
 
+    #[test]
+    fn frontier_stats() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+
+        let mut solver = BreadthFirst::new();
+        solver.solve(&round, pos.clone());
+        assert_eq!(solver.frontier_stats(), None);
+
+        let mut solver = BreadthFirst::with_frontier_stats();
+        let path = solver.solve(&round, pos);
+        let stats = solver.frontier_stats().unwrap();
+        assert_eq!(stats.len(), path.len());
+        assert_eq!(stats[0].frontier, 1 + stats[0].visited);
+        assert!(stats.windows(2).all(|w| w[0].visited < w[1].visited));
+    }
+
+    #[test]
+    fn best_first_within_depth() {
+        let (pos, game) = create_board();
+        for (target, target_position) in game.iter_targets() {
+            let round = Round::new(game.board().clone(), target, target_position);
+            let mut solver = BreadthFirst::new();
+            let path = solver.solve(&round, pos.clone());
+            let mut sorted = BreadthFirst::new().best_first_within_depth(true);
+            let sorted_path = sorted.solve(&round, pos.clone());
+
+            assert!(sorted_path.verify(&round));
+            assert_eq!(sorted_path.len(), path.len());
+            assert!(sorted.last_stats().nodes_expanded() <= solver.last_stats().nodes_expanded());
+        }
+    }
+
+    #[test]
+    fn forbidden_stops() {
+        let (pos, game) = create_board();
+        let target = Target::Red(Symbol::Triangle);
+        let round = round_on(&game, target);
+        let path = BreadthFirst::new().solve(&round, pos.clone());
+
+        // Forbid all fields the robots stop on before reaching the target.
+        let forbidden = path.positions(&round)[1..path.len()]
+            .iter()
+            .flat_map(|positions| positions.to_array().to_vec())
+            .filter(|&field| !pos.contains_any_robot(field))
+            .collect::<HashSet<_>>();
+        for &rule in [
+            ForbiddenStopRule::Disallowed,
+            ForbiddenStopRule::PassThrough,
+        ]
+        .iter()
+        {
+            let restricted = round.clone().with_forbidden_stops(forbidden.clone(), rule);
+            let restricted_path = BreadthFirst::new().solve(&restricted, pos.clone());
+            assert!(restricted_path.len() >= path.len());
+
+            let mut current = pos.clone();
+            for &(robot, direction) in restricted_path.movements() {
+                current = restricted.move_robot(&current, robot, direction).unwrap();
+                assert!(!forbidden.contains(&current[robot]));
+            }
+            assert_eq!(&current, restricted_path.end_pos());
+            assert!(restricted.target_reached(&current));
+        }
+    }
+
     #[test]
     #[ignore]
     fn solve_many() {
         let (_, game) = create_board();
 
-        let targets: Vec<_> = game.targets().iter().map(|(target, _)| target).collect();
+        let targets: Vec<_> = game.iter_targets().map(|(target, _)| target).collect();
 
         let uniform = Uniform::from(0..16);
         let rng = rand::rngs::StdRng::seed_from_u64(1);
@@ -235,11 +416,11 @@ mod tests {
 
         let mut tests = samples
             .par_iter()
-            .map(|(pos, &target)| {
-                let target_position = game.get_target_position(&target).expect("unknown target");
-                let round = Round::new(game.board().clone(), target, target_position);
+            .map(|(pos, target)| {
+                let target_position = game.get_target_position(target).expect("unknown target");
+                let round = Round::new(game.board().clone(), *target, target_position);
                 let solution = BreadthFirst::new().solve(&round, pos.clone());
-                PositionTest::new(pos.clone(), target, solution.end_pos, solution.movements)
+                PositionTest::new(pos.clone(), *target, solution.end_pos, solution.movements)
             })
             .collect::<Vec<_>>();
 
@@ -0,0 +1,85 @@
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
+
+use crate::{replay_move, AStar, Path, Solver};
+
+/// The result of comparing a solution found by a player to an optimal solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    /// The path made up of the moves of the player.
+    pub user_path: Path,
+    /// An optimal path found by a solver.
+    pub optimal_path: Path,
+}
+
+impl Comparison {
+    /// Returns the number of moves the player needed more than an optimal solution.
+    pub fn difference(&self) -> usize {
+        self.user_path.len() - self.optimal_path.len()
+    }
+
+    /// Checks if the player found a solution with the least possible number of moves.
+    pub fn is_optimal(&self) -> bool {
+        self.difference() == 0
+    }
+}
+
+/// Checks whether the `user_moves` reach the target of `round` starting from `start_positions`
+/// and compares them to an optimal solution.
+///
+/// # Errors
+///
+/// Returns an error if a move ends on one of the [`forbidden_stops`](Round::forbidden_stops) of
+/// `round` or the target hasn't been reached after the last move.
+pub fn compare_to_optimal(
+    round: &Round,
+    user_moves: &[(Robot, Direction)],
+    start_positions: RobotPositions,
+) -> Result<Comparison, &'static str> {
+    let end_positions = user_moves
+        .iter()
+        .try_fold(start_positions.clone(), |pos, &(robot, direction)| {
+            replay_move(round, pos, robot, direction)
+        })
+        .ok_or("A move ends on a field robots may not stop on")?;
+    if !round.target_reached(&end_positions) {
+        return Err("The moves don't reach the target");
+    }
+
+    let user_path = Path::new(start_positions.clone(), end_positions, user_moves.to_vec());
+    let optimal_path = AStar::new().solve(round, start_positions);
+    Ok(Comparison {
+        user_path,
+        optimal_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{Direction, Robot, Symbol, Target};
+
+    use super::compare_to_optimal;
+    use crate::tests::create_round;
+    use crate::{AStar, Solver};
+
+    #[test]
+    fn optimal_moves() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let optimal = AStar::new().solve(&round, pos.clone());
+
+        let comparison = compare_to_optimal(&round, optimal.movements(), pos.clone()).unwrap();
+        assert!(comparison.is_optimal());
+        assert_eq!(comparison.difference(), 0);
+
+        let prefix = [(Robot::Blue, Direction::Up), (Robot::Blue, Direction::Down)];
+        let longer = AStar::new().solve_with_prefix(&round, pos.clone(), &prefix);
+        let comparison = compare_to_optimal(&round, longer.movements(), pos).unwrap();
+        assert_eq!(comparison.difference(), longer.len() - optimal.len());
+        assert!(!comparison.is_optimal());
+    }
+
+    #[test]
+    fn missed_target() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        assert!(compare_to_optimal(&round, &[(Robot::Red, Direction::Down)], pos).is_err());
+    }
+}
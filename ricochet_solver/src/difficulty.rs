@@ -0,0 +1,451 @@
+use fxhash::FxHashSet;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use ricochet_board::quadrant::WallDirection;
+use ricochet_board::{
+    quadrant, Game, Position, PositionEncoding, Robot, RobotPositions, Round, Target, ROBOTS,
+};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::ops::Range;
+
+use crate::util::LeastMovesBoard;
+use crate::{AStar, MinRobots, Path, Solver};
+
+/// How hard it is to find the optimal solution of a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Solvable with few moves of few robots.
+    Easy,
+    /// Needs more moves or robots than an easy round.
+    Medium,
+    /// Exceeds the thresholds of a medium round.
+    Hard,
+}
+
+/// Thresholds used to classify the [`Difficulty`](Difficulty) of a round.
+///
+/// A round is classified by its optimal solution. It is easy if neither the number of moves nor
+/// the number of robots moved exceed the thresholds for easy rounds. The same applies to medium
+/// rounds, all other rounds are hard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DifficultyConfig {
+    /// The maximum number of moves in an easy round.
+    pub easy_max_moves: usize,
+    /// The maximum number of robots moved in an easy round.
+    pub easy_max_robots: usize,
+    /// The maximum number of moves in a medium round.
+    pub medium_max_moves: usize,
+    /// The maximum number of robots moved in a medium round.
+    pub medium_max_robots: usize,
+}
+
+impl DifficultyConfig {
+    /// Solves the `round` optimally and classifies it by the found solution.
+    pub fn classify(&self, round: &Round, start_positions: RobotPositions) -> Difficulty {
+        let path = AStar::new().solve(round, start_positions);
+        self.classify_path(&path)
+    }
+
+    /// Classifies an already known optimal `path`.
+    pub fn classify_path(&self, path: &Path) -> Difficulty {
+        let moves = path.len();
+        let robots = path.robot_count();
+
+        if moves <= self.easy_max_moves && robots <= self.easy_max_robots {
+            Difficulty::Easy
+        } else if moves <= self.medium_max_moves && robots <= self.medium_max_robots {
+            Difficulty::Medium
+        } else {
+            Difficulty::Hard
+        }
+    }
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self {
+            easy_max_moves: 4,
+            easy_max_robots: 1,
+            medium_max_moves: 8,
+            medium_max_robots: 2,
+        }
+    }
+}
+
+/// Classifies the `round` starting from `start_positions` with the default
+/// [`DifficultyConfig`](DifficultyConfig).
+pub fn classify_difficulty(round: &Round, start_positions: RobotPositions) -> Difficulty {
+    DifficultyConfig::default().classify(round, start_positions)
+}
+
+/// Solves the `round` optimally from each of the `starts` and counts how often each solution
+/// length occurs.
+///
+/// The returned map contains the number of starting positions for each optimal length. The rounds
+/// are solved in parallel.
+pub fn length_histogram(round: &Round, starts: &[RobotPositions]) -> BTreeMap<usize, usize> {
+    starts
+        .par_iter()
+        .map(|start| AStar::new().solve(round, start.clone()).len())
+        .fold(BTreeMap::new, |mut histogram, length| {
+            *histogram.entry(length).or_insert(0) += 1;
+            histogram
+        })
+        .reduce(BTreeMap::new, |mut histogram, other| {
+            for (length, count) in other {
+                *histogram.entry(length).or_insert(0) += count;
+            }
+            histogram
+        })
+}
+
+/// Counts the distinct final positions of all optimal solutions of the `round` starting from
+/// `start_positions`.
+///
+/// A breadth first search is run up to the length of an optimal solution and all positions
+/// reached at that depth in which the target is reached are counted. Different move sequences
+/// ending on the same positions are only counted once, so this is a lower bound of the number of
+/// optimal move sequences but much cheaper than enumerating them. A round with a count of 1 has
+/// a unique optimal result, although it may still be reached in different ways.
+///
+/// Returns 1 if the target is already reached at the start.
+pub fn count_optimal_solutions(round: &Round, start_positions: RobotPositions) -> usize {
+    if round.target_reached(&start_positions) {
+        return 1;
+    }
+
+    let mut visited = FxHashSet::default();
+    visited.insert(start_positions.clone());
+    let mut current = vec![start_positions];
+    loop {
+        let mut next = Vec::new();
+        for pos in &current {
+            for (new_pos, _) in round.reachable_positions(pos) {
+                if visited.insert(new_pos.clone()) {
+                    next.push(new_pos);
+                }
+            }
+        }
+
+        let solutions = next.iter().filter(|pos| round.target_reached(pos)).count();
+        if solutions > 0 || next.is_empty() {
+            return solutions;
+        }
+        current = next;
+    }
+}
+
+/// Finds the first standard board created by [`game_from_seed`](quadrant::game_from_seed) with a
+/// seed in `seeds`, on which reaching `target` from `start_positions` needs exactly `moves` moves.
+///
+/// The boards are solved in parallel, but the game with the lowest matching seed is returned.
+/// Returns `None` if none of the boards matches.
+pub fn find_board_with_length(
+    target: Target,
+    start_positions: &RobotPositions,
+    moves: usize,
+    seeds: Range<usize>,
+) -> Option<Game> {
+    seeds.into_par_iter().find_map_first(|seed| {
+        let game = quadrant::game_from_seed(seed);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target)?,
+        );
+        let length = AStar::new().solve(&round, start_positions.clone()).len();
+        Some(game).filter(|_| length == moves)
+    })
+}
+
+/// Returns the lower bound of the number of moves used by the heuristic solvers and the length of
+/// an optimal solution starting from `start_positions`.
+///
+/// The lower bound is calculated with a [`LeastMovesBoard`](LeastMovesBoard), so the difference
+/// between both values shows how far the heuristic is from the actual number of moves.
+pub fn heuristic_gap(round: &Round, start_positions: RobotPositions) -> (usize, usize) {
+    let move_board = LeastMovesBoard::new(round.board(), round.target_position());
+    let lower_bound = round.lower_bound(&move_board, &start_positions);
+    let length = AStar::new().solve(round, start_positions).len();
+    (lower_bound, length)
+}
+
+/// Returns the seeds of all distinct standard boards created by
+/// [`game_from_seed`](quadrant::game_from_seed) which place `target` on `position`.
+///
+/// All [`DISTINCT_STANDARD_BOARDS`](quadrant::DISTINCT_STANDARD_BOARDS) boards are created in
+/// parallel, the seeds are returned in ascending order.
+pub fn seeds_with_target_at(target: Target, position: Position) -> Vec<usize> {
+    (0..quadrant::DISTINCT_STANDARD_BOARDS)
+        .into_par_iter()
+        .filter(|&seed| {
+            quadrant::game_from_seed(seed).get_target_position(&target) == Some(position)
+        })
+        .collect()
+}
+
+/// The number of rounds [`random_round_with_length`](random_round_with_length) tries before giving
+/// up.
+pub const RANDOM_ROUND_ATTEMPTS: usize = 1000;
+
+/// Finds a random standard round with starting positions, which needs exactly `moves` moves to be
+/// solved optimally.
+///
+/// The candidates are created by [`round_and_start_from_seed`](quadrant::round_and_start_from_seed)
+/// with seeds drawn from a random number generator seeded with `seed`, so the same `seed` always
+/// returns the same round. The candidates are solved in parallel, returns `None` if none of the
+/// first [`RANDOM_ROUND_ATTEMPTS`](RANDOM_ROUND_ATTEMPTS) candidates matches.
+pub fn random_round_with_length(seed: u64, moves: usize) -> Option<(Round, RobotPositions)> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    let seeds: Vec<(usize, u64)> = (0..RANDOM_ROUND_ATTEMPTS)
+        .map(|_| {
+            (
+                rng.gen_range(0..quadrant::DISTINCT_STANDARD_ROUNDS),
+                rng.gen(),
+            )
+        })
+        .collect();
+
+    seeds
+        .into_par_iter()
+        .find_map_first(|(round_seed, start_seed)| {
+            let (round, start) = quadrant::round_and_start_from_seed(round_seed, start_seed);
+            let length = AStar::new().solve(&round, start.clone()).len();
+            Some((round, start)).filter(|_| length == moves)
+        })
+}
+
+/// Finds all walls of the `round`'s board which don't change the length of an optimal solution
+/// starting from `start_positions` if they are removed.
+///
+/// Each wall is removed on its own and the resulting round is solved again with the same
+/// forbidden stops and condition, which is done in parallel. Walls without which the target can't
+/// be reached anymore are not redundant. The walls enclosing the board are skipped, since removing
+/// them connects opposite edges of the board. The walls are returned ordered by their field, going
+/// through the fields column by column.
+pub fn redundant_walls(
+    round: &Round,
+    start_positions: RobotPositions,
+) -> Vec<(Position, WallDirection)> {
+    let length = AStar::new().solve(round, start_positions.clone()).len();
+    let board = round.board();
+    let last = board.side_length() - 1;
+
+    let mut walls = Vec::new();
+    for (column, fields) in board.get_walls().iter().enumerate() {
+        for (row, field) in fields.iter().enumerate() {
+            let pos = Position::new(column as PositionEncoding, row as PositionEncoding);
+            if field.right && pos.column() != last {
+                walls.push((pos, WallDirection::Right));
+            }
+            if field.down && pos.row() != last {
+                walls.push((pos, WallDirection::Down));
+            }
+        }
+    }
+
+    walls
+        .into_par_iter()
+        .filter(|&(pos, direction)| {
+            let mut board = board.clone();
+            match direction {
+                WallDirection::Right => board[pos].right = false,
+                WallDirection::Down => board[pos].down = false,
+            }
+            let changed = Round::new(board, round.target(), round.target_position())
+                .with_forbidden_stops(round.forbidden_stops().clone(), round.forbidden_stop_rule());
+            let changed = match round.condition() {
+                Some(condition) => changed.with_condition(condition.clone()),
+                None => changed,
+            };
+
+            // Without the wall the target may not be reachable anymore, e.g. if it was stopping
+            // the robots on the target.
+            let move_board = LeastMovesBoard::new(changed.board(), changed.target_position());
+            if move_board.is_impossible_bound(changed.lower_bound(&move_board, &start_positions)) {
+                return false;
+            }
+            AStar::new().solve(&changed, start_positions.clone()).len() == length
+        })
+        .collect()
+}
+
+/// Checks if `robot` is needed to reach the target of the `round` starting from
+/// `start_positions` in the optimal number of moves.
+///
+/// The round is solved once normally and once with `robot` pinned to its starting field, where it
+/// still blocks the other robots. The robot is necessary if the second solution is longer or the
+/// target can't be reached at all.
+pub fn is_robot_necessary(round: &Round, start_positions: RobotPositions, robot: Robot) -> bool {
+    if round.target_reached(&start_positions) {
+        return false;
+    }
+    // Without a custom condition only the target's robot can reach it, which saves searching all
+    // positions of the other robots.
+    if round.condition().is_none() && Robot::try_from(round.target()) == Ok(robot) {
+        return true;
+    }
+
+    let length = AStar::new().solve(round, start_positions.clone()).len();
+    let others = ROBOTS
+        .iter()
+        .copied()
+        .filter(|&other| other != robot)
+        .collect::<Vec<_>>();
+    match MinRobots::new().search(round, &start_positions, &others, None) {
+        Ok(Some(path)) => path.len() > length,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::quadrant::WallDirection;
+    use ricochet_board::{
+        quadrant, round, Position, Robot, RobotPositions, Symbol, Target, ROBOTS,
+    };
+
+    use super::{
+        classify_difficulty, count_optimal_solutions, find_board_with_length, heuristic_gap,
+        is_robot_necessary, length_histogram, random_round_with_length, redundant_walls,
+        seeds_with_target_at, Difficulty, DifficultyConfig,
+    };
+    use crate::tests::{create_board, create_round, round_on};
+    use crate::{AStar, Solver};
+
+    #[test]
+    fn easy_round() {
+        let (pos, round) = create_round(Target::Red(Symbol::Triangle));
+        assert_eq!(classify_difficulty(&round, pos), Difficulty::Easy);
+    }
+
+    #[test]
+    fn hard_round() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        assert_eq!(classify_difficulty(&round, pos), Difficulty::Hard);
+    }
+
+    #[test]
+    fn custom_thresholds() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let config = DifficultyConfig {
+            medium_max_moves: 9,
+            medium_max_robots: 3,
+            ..Default::default()
+        };
+        assert_eq!(config.classify(&round, pos), Difficulty::Medium);
+    }
+
+    #[test]
+    fn histogram() {
+        let (pos, round) = create_round(Target::Red(Symbol::Triangle));
+        let length = AStar::new().solve(&round, pos.clone()).len();
+        let on_target =
+            RobotPositions::from_tuples(&[round.target_position().into(), (5, 4), (7, 1), (7, 15)]);
+
+        let histogram = length_histogram(&round, &[pos.clone(), on_target, pos]);
+        assert_eq!(
+            histogram.into_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (length, 2)]
+        );
+    }
+
+    #[test]
+    fn optimal_solution_count() {
+        let (pos, game) = create_board();
+        let round = round_on(&game, Target::Red(Symbol::Triangle));
+        assert_eq!(count_optimal_solutions(&round, pos.clone()), 1);
+        let many = round_on(&game, Target::Red(Symbol::Square));
+        assert_eq!(count_optimal_solutions(&many, pos.clone()), 5);
+
+        let optimal = AStar::new().solve(&round, pos);
+        let on_target = optimal.end_pos().clone();
+        assert_eq!(count_optimal_solutions(&round, on_target), 1);
+    }
+
+    #[test]
+    fn board_with_length() {
+        let (pos, _) = create_board();
+        let target = Target::Green(Symbol::Circle);
+        let game = find_board_with_length(target, &pos, 4, 0..20).unwrap();
+        assert_eq!(game, quadrant::game_from_seed(2));
+        let round = round_on(&game, target);
+        assert_eq!(AStar::new().solve(&round, pos.clone()).len(), 4);
+
+        assert_eq!(find_board_with_length(target, &pos, 100, 0..5), None);
+    }
+
+    #[test]
+    fn walls_without_effect() {
+        let (pos, round) = create_round(Target::Red(Symbol::Triangle));
+        let redundant = redundant_walls(&round, pos);
+        assert_eq!(redundant.len(), 48);
+        // The red robot is stopped on the target by this wall.
+        assert!(!redundant.contains(&(Position::new(1, 3), WallDirection::Down)));
+        assert!(redundant.contains(&(Position::new(0, 3), WallDirection::Right)));
+
+        // Without its wall the target can't be stopped on anymore.
+        let (round, pos) = round!(
+            Target::Red(Symbol::Square),
+            "
+            . . . .
+            R *|. .
+            . . . .
+            B G . Y
+            "
+        );
+        assert!(redundant_walls(&round, pos).is_empty());
+    }
+
+    #[test]
+    fn random_round_length() {
+        let (round, start) = random_round_with_length(0, 6).unwrap();
+        assert_eq!(AStar::new().solve(&round, start.clone()).len(), 6);
+        assert_eq!(random_round_with_length(0, 6), Some((round, start)));
+    }
+
+    #[test]
+    fn gap_of_heuristic() {
+        let (pos, game) = create_board();
+        for target in game.targets().keys().copied() {
+            let round = round_on(&game, target);
+            let (lower_bound, length) = heuristic_gap(&round, pos.clone());
+            assert!(lower_bound <= length);
+        }
+
+        let round = round_on(&game, Target::Yellow(Symbol::Hexagon));
+        let length = AStar::new().solve(&round, pos.clone()).len();
+        assert_eq!(heuristic_gap(&round, pos), (2, length));
+    }
+
+    #[test]
+    fn target_positions() {
+        let target = Target::Green(Symbol::Circle);
+        let position = quadrant::game_from_seed(2)
+            .get_target_position(&target)
+            .unwrap();
+        let seeds = seeds_with_target_at(target, position);
+        assert!(seeds.contains(&2));
+        assert!(seeds.windows(2).all(|w| w[0] < w[1]));
+        assert!(seeds.len() < quadrant::DISTINCT_STANDARD_BOARDS);
+        for seed in seeds {
+            assert_eq!(
+                quadrant::game_from_seed(seed).get_target_position(&target),
+                Some(position)
+            );
+        }
+        assert!(seeds_with_target_at(target, Position::new(7, 7)).is_empty());
+    }
+
+    #[test]
+    fn necessary_robots() {
+        let (pos, round) = create_round(Target::Red(Symbol::Square));
+        let necessary = ROBOTS
+            .iter()
+            .filter(|&&robot| is_robot_necessary(&round, pos.clone(), robot))
+            .collect::<Vec<_>>();
+        // The optimal solution moves yellow, but another one with seven moves doesn't need it.
+        assert_eq!(necessary, vec![&Robot::Red]);
+    }
+}
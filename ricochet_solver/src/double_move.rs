@@ -0,0 +1,136 @@
+use fxhash::FxHashMap;
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
+
+use crate::{start_is_solution, Path};
+
+/// Maps each visited position to the position it was reached from and the moves of the turn.
+type VisitedTurns = FxHashMap<RobotPositions, (RobotPositions, Vec<(Robot, Direction)>)>;
+
+/// Finds a solution with the fewest turns in the double move variant of the rules.
+///
+/// In this variant a turn moves a single robot once or twice in a row, so moving the same robot
+/// in two directions costs one turn, while moving two different robots costs two turns. Returns
+/// the path listing each move on its own and the number of turns it takes. The number of turns is
+/// at least half the number of moves of an optimal solution, but the path may need more moves than
+/// an optimal solution of the standard rules.
+///
+/// The turns are searched breadth first, taking the
+/// [`forbidden_stops`](Round::forbidden_stops) of the round into account for every move.
+///
+/// # Panics
+/// Panics if the target can't be reached from `start_positions`.
+pub fn solve_double_move(round: &Round, start_positions: RobotPositions) -> (Path, usize) {
+    if let Some(path) = start_is_solution(round, &start_positions) {
+        return (path, 0);
+    }
+
+    let mut visited = VisitedTurns::default();
+    visited.insert(
+        start_positions.clone(),
+        (start_positions.clone(), Vec::new()),
+    );
+    let mut current = vec![start_positions.clone()];
+
+    for turn in 1.. {
+        let mut next = Vec::new();
+        for pos in &current {
+            let single_moves: Vec<_> = round
+                .reachable_positions(pos)
+                .map(|(new_pos, movement)| (new_pos, vec![movement]))
+                .collect();
+            let double_moves: Vec<_> = single_moves
+                .iter()
+                .flat_map(|(first_pos, first)| {
+                    round
+                        .reachable_positions(first_pos)
+                        .filter(move |(_, (robot, _))| *robot == first[0].0)
+                        .map(move |(new_pos, second)| (new_pos, vec![first[0], second]))
+                })
+                .collect();
+
+            for (new_pos, moves) in single_moves.into_iter().chain(double_moves) {
+                if visited.contains_key(&new_pos) {
+                    continue;
+                }
+                visited.insert(new_pos.clone(), (pos.clone(), moves));
+                if round.target_reached(&new_pos) {
+                    return (path_to(&visited, &start_positions, new_pos), turn);
+                }
+                next.push(new_pos);
+            }
+        }
+
+        if next.is_empty() {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+        current = next;
+    }
+
+    unreachable!()
+}
+
+/// Creates the path from `start_positions` to `end_pos` by following the turns stored in
+/// `visited` backwards.
+fn path_to(
+    visited: &VisitedTurns,
+    start_positions: &RobotPositions,
+    end_pos: RobotPositions,
+) -> Path {
+    let mut turns = Vec::new();
+    let mut pos = &end_pos;
+    while pos != start_positions {
+        let (previous, moves) = &visited[pos];
+        turns.push(moves);
+        pos = previous;
+    }
+
+    let movements = turns.into_iter().rev().flatten().copied().collect();
+    Path::new(start_positions.clone(), end_pos, movements)
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, round, RobotPositions, Round, Symbol, Target};
+
+    use super::solve_double_move;
+    use crate::{AStar, Solver};
+
+    #[test]
+    fn one_robot_per_turn() {
+        let (round, start) = round!(
+            Target::Red(Symbol::Square),
+            "
+            R . . .
+            . . . .
+            . . . *
+            B G . Y
+            "
+        );
+        let (path, turns) = solve_double_move(&round, start);
+        assert!(path.verify(&round));
+        assert_eq!((path.len(), turns), (2, 1));
+        assert_eq!(path.robot_count(), 1);
+    }
+
+    #[test]
+    fn fewer_turns_than_moves() {
+        let game = quadrant::game_from_seed(0);
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        for (target, position) in game.iter_targets() {
+            let round = Round::new(game.board().clone(), target, position);
+            let optimal = AStar::new().solve(&round, start.clone()).len();
+            let (path, turns) = solve_double_move(&round, start.clone());
+
+            assert!(path.verify(&round));
+            assert!(turns <= optimal);
+            assert!(turns * 2 >= optimal);
+            // Every change of the moved robot starts a new turn.
+            let robot_changes = path
+                .movements()
+                .windows(2)
+                .filter(|w| w[0].0 != w[1].0)
+                .count();
+            assert!(robot_changes < turns);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::util::LeastMovesBoard;
+use crate::{is_cancelled, start_is_solution, Path, SolveError, SolveStats, Solver};
+
+/// A solver which quickly finds any path to the target by always making the move that seems to
+/// bring the robot closest to the target.
+///
+/// The found paths are usually far from optimal, so `Greedy` is only useful to check whether a
+/// round is solvable or to get an upper bound on the length of an optimal solution.
+///
+/// The moves are rated with a [`LeastMovesBoard`](LeastMovesBoard) and ties are broken randomly.
+/// With a small probability a random move is made instead. Positions are never visited twice in the
+/// same attempt, if no unvisited position is reachable or the path gets too long, a new attempt is
+/// started from the beginning. After [`max_attempts`](Greedy::with_max_attempts) failed attempts
+/// the solver gives up with [`SolveError::NoSolutionFound`](SolveError::NoSolutionFound).
+#[derive(Debug)]
+pub struct Greedy {
+    move_board: LeastMovesBoard,
+    random_move_probability: f64,
+    max_path_length: usize,
+    max_attempts: usize,
+    seed: u64,
+    stats: SolveStats,
+}
+
+impl Greedy {
+    /// Creates a new, randomly seeded `Greedy` solver.
+    pub fn new() -> Self {
+        Self::new_seeded(rand::random())
+    }
+
+    /// Creates a new `Greedy` solver with the given seed.
+    pub fn new_seeded(seed: u64) -> Self {
+        Self {
+            move_board: Default::default(),
+            random_move_probability: 0.1,
+            max_path_length: 64,
+            max_attempts: 4096,
+            seed,
+            stats: Default::default(),
+        }
+    }
+
+    /// Sets the number of attempts made before giving up, which is 4096 by default.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Tries to reach the target from `start_positions` once.
+    ///
+    /// Returns `None` if the attempt got stuck or the path got longer than `self.max_path_length`.
+    fn attempt(
+        &mut self,
+        round: &Round,
+        start_positions: &RobotPositions,
+        rng: &mut impl Rng,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Option<Path>, SolveError> {
+        let mut visited = HashSet::new();
+        visited.insert(start_positions.clone());
+        let mut current_pos = start_positions.clone();
+        let mut movements: Vec<(Robot, Direction)> = Vec::new();
+
+        while movements.len() < self.max_path_length {
+            if is_cancelled(cancel) {
+                return Err(SolveError::Cancelled);
+            }
+            self.stats.expand(movements.len());
+
+            let mut children = round
+                .reachable_positions(&current_pos)
+                .filter(|(pos, _)| !visited.contains(pos))
+                .collect::<Vec<_>>();
+            if children.is_empty() {
+                return Ok(None);
+            }
+
+            if let Some((pos, movement)) = children
+                .iter()
+                .find(|(pos, _)| round.target_reached(pos))
+                .cloned()
+            {
+                movements.push(movement);
+                return Ok(Some(Path::new(start_positions.clone(), pos, movements)));
+            }
+
+            // Shuffle first to break ties randomly.
+            children.shuffle(rng);
+            let (pos, movement) = if rng.gen_bool(self.random_move_probability) {
+                children.swap_remove(0)
+            } else {
+                children
+                    .into_iter()
+                    .min_by_key(|(pos, _)| round.lower_bound(&self.move_board, pos))
+                    .expect("Failed to find the best move")
+            };
+
+            visited.insert(pos.clone());
+            movements.push(movement);
+            current_pos = pos;
+        }
+
+        Ok(None)
+    }
+
+    /// Searches for a path like [`solve_cancellable`](Solver::solve_cancellable) without measuring
+    /// the elapsed time.
+    fn find_path(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Path, SolveError> {
+        self.stats = Default::default();
+
+        if let Some(path) = start_is_solution(round, &start_positions) {
+            return Ok(path);
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_impossible_bound(round.lower_bound(&self.move_board, &start_positions))
+        {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+        for _ in 0..self.max_attempts {
+            if let Some(path) = self.attempt(round, &start_positions, &mut rng, cancel)? {
+                return Ok(path);
+            }
+        }
+        Err(SolveError::NoSolutionFound)
+    }
+}
+
+impl Solver for Greedy {
+    fn solve_cancellable(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Path, SolveError> {
+        let started = Instant::now();
+        let result = self.find_path(round, start_positions, cancel.as_deref());
+        self.stats.elapsed = started.elapsed();
+        result
+    }
+
+    fn last_stats(&self) -> SolveStats {
+        self.stats
+    }
+
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+}
+
+impl Default for Greedy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The number of attempts made by [`greedy_upper_bound`](greedy_upper_bound) before giving up.
+pub const GREEDY_UPPER_BOUND_ATTEMPTS: usize = 256;
+
+/// Returns the length of a solution found by the [`Greedy`](Greedy) solver, which is an upper
+/// bound of the length of an optimal solution.
+///
+/// Only [`GREEDY_UPPER_BOUND_ATTEMPTS`](GREEDY_UPPER_BOUND_ATTEMPTS) attempts are made instead of
+/// the default of the solver, so this returns quickly. The solver is seeded with a fixed seed to
+/// return the same bound for the same round. Returns `None` if all attempts got stuck or the
+/// target can't be reached.
+pub fn greedy_upper_bound(round: &Round, start_positions: RobotPositions) -> Option<usize> {
+    if round.target_reached(&start_positions) {
+        return Some(0);
+    }
+
+    let move_board = LeastMovesBoard::new(round.board(), round.target_position());
+    if move_board.is_impossible_bound(round.lower_bound(&move_board, &start_positions)) {
+        return None;
+    }
+
+    Greedy::new_seeded(0)
+        .with_max_attempts(GREEDY_UPPER_BOUND_ATTEMPTS)
+        .solve_cancellable(round, start_positions, None)
+        .ok()
+        .map(|path| path.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{RobotPositions, Round, Symbol, Target, TargetCondition};
+    use std::sync::Arc;
+
+    use super::greedy_upper_bound;
+    use crate::tests::{create_board, round_on};
+    use crate::{AStar, Greedy, Path, SolveError, Solver};
+
+    // Test robot already on target
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new_start_on_target(start.clone());
+        assert_eq!(Greedy::new_seeded(0).solve(&round, start), expected);
+    }
+
+    #[test]
+    fn solve() {
+        let (pos, game) = create_board();
+        for target in game.targets().keys().copied() {
+            let round = round_on(&game, target);
+
+            let path = Greedy::new_seeded(0).solve(&round, pos.clone());
+            assert!(round.target_reached(path.end_pos()));
+            assert_eq!(path.positions(&round).last(), Some(path.end_pos()));
+        }
+    }
+
+    #[test]
+    fn upper_bound() {
+        let (pos, game) = create_board();
+        for target in game.targets().keys().copied() {
+            let round = round_on(&game, target);
+            let bound = greedy_upper_bound(&round, pos.clone()).unwrap();
+            assert!(bound >= AStar::new().solve(&round, pos.clone()).len());
+            assert_eq!(greedy_upper_bound(&round, pos.clone()), Some(bound));
+        }
+    }
+
+    #[test]
+    fn gives_up() {
+        struct Never;
+        impl TargetCondition for Never {
+            fn is_satisfied(&self, _: &RobotPositions) -> bool {
+                false
+            }
+        }
+
+        let (pos, game) = create_board();
+        let target = Target::Red(Symbol::Triangle);
+        let round = round_on(&game, target).with_condition(Arc::new(Never));
+
+        let result = Greedy::new_seeded(0)
+            .with_max_attempts(8)
+            .solve_cancellable(&round, pos.clone(), None);
+        assert_eq!(result, Err(SolveError::NoSolutionFound));
+        assert_eq!(greedy_upper_bound(&round, pos), None);
+    }
+}
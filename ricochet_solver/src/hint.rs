@@ -0,0 +1,91 @@
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
+
+use crate::{AStar, Solver};
+
+/// How much of an optimal solution a [`Hint`](Hint) reveals.
+///
+/// The levels are ordered from revealing the least to revealing the most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HintLevel {
+    /// Only the number of moves needed.
+    MoveCount,
+    /// The robot which has to be moved first.
+    FirstRobot,
+    /// The first move.
+    FirstMove,
+    /// The given number of moves from the start of the solution.
+    Moves(usize),
+}
+
+/// A hint towards an optimal solution of a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hint {
+    /// The target is already reached, no moves are needed.
+    Solved,
+    /// The number of moves an optimal solution needs.
+    MoveCount(usize),
+    /// The robot moved first in an optimal solution.
+    FirstRobot(Robot),
+    /// The first moves of an optimal solution.
+    Moves(Vec<(Robot, Direction)>),
+}
+
+/// Solves the `round` optimally starting from `start_positions` and reveals as much of the
+/// solution as allowed by `level`.
+///
+/// [`HintLevel::Moves`](HintLevel::Moves) reveals at most the whole solution. If the round has
+/// multiple optimal solutions, the hint is derived from the one found by [`AStar`](AStar).
+pub fn hint(round: &Round, start_positions: RobotPositions, level: HintLevel) -> Hint {
+    let path = AStar::new().solve(round, start_positions);
+    let first_move = match path.first_move() {
+        Some(first_move) => first_move,
+        None => return Hint::Solved,
+    };
+
+    match level {
+        HintLevel::MoveCount => Hint::MoveCount(path.len()),
+        HintLevel::FirstRobot => Hint::FirstRobot(first_move.0),
+        HintLevel::FirstMove => Hint::Moves(vec![first_move]),
+        HintLevel::Moves(n) => Hint::Moves(path.movements().iter().take(n).copied().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{Symbol, Target};
+
+    use super::{hint, Hint, HintLevel};
+    use crate::tests::create_round;
+    use crate::{AStar, Solver};
+
+    #[test]
+    fn hint_levels() {
+        let (pos, round) = create_round(Target::Red(Symbol::Square));
+        let path = AStar::new().solve(&round, pos.clone());
+        let movements = path.movements();
+
+        assert_eq!(
+            hint(&round, pos.clone(), HintLevel::MoveCount),
+            Hint::MoveCount(path.len())
+        );
+        assert_eq!(
+            hint(&round, pos.clone(), HintLevel::FirstRobot),
+            Hint::FirstRobot(movements[0].0)
+        );
+        assert_eq!(
+            hint(&round, pos.clone(), HintLevel::FirstMove),
+            Hint::Moves(movements[..1].to_vec())
+        );
+        assert_eq!(
+            hint(&round, pos.clone(), HintLevel::Moves(2)),
+            Hint::Moves(movements[..2].to_vec())
+        );
+        assert_eq!(
+            hint(&round, pos, HintLevel::Moves(100)),
+            Hint::Moves(movements.clone())
+        );
+
+        let on_target = path.end_pos().clone();
+        assert_eq!(hint(&round, on_target, HintLevel::FirstMove), Hint::Solved);
+    }
+}
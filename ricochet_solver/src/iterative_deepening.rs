@@ -1,7 +1,10 @@
 use ricochet_board::{RobotPositions, Round};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNodes};
-use crate::{Path, Solver};
+use crate::{is_cancelled, start_is_solution, Path, SolveError, SolveStats, Solver};
 
 /// A solver using the iterative deepening (IDA* ) algorithm to find the shortest path to the
 /// target.
@@ -20,33 +23,36 @@ pub struct IdaStar {
     /// This minimum is a lower bound and may be impossible to reach even if all other robots are
     /// positioned perfectly.
     move_board: LeastMovesBoard,
+    /// Whether `move_board` has been supplied by the caller and should be reused.
+    supplied_move_board: bool,
+    /// Statistics of the last search.
+    stats: SolveStats,
 }
 
 impl Solver for IdaStar {
-    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
-        // Check if the robot has already reached the target
-        if round.target_reached(&start_positions) {
-            return Path::new_start_on_target(start_positions);
-        }
+    fn solve_cancellable(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Path, SolveError> {
+        let started = Instant::now();
+        let result = self.find_path(round, start_positions, cancel.as_deref());
+        self.stats.elapsed = started.elapsed();
+        result
+    }
 
-        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
-        let start = self.move_board.min_moves(&start_positions, round.target());
+    fn last_stats(&self) -> SolveStats {
+        self.stats
+    }
 
-        if self
-            .move_board
-            .is_unsolvable(&start_positions, round.target())
-        {
-            panic!("It's not possible to reach the target starting from this robot configuration");
-        }
+    fn name(&self) -> &'static str {
+        "ida-star"
+    }
 
-        for i in start.. {
-            let maybe = self.depth_limited_dfs(round, start_positions.clone(), 0, i);
-            if let Some(final_pos) = maybe {
-                return self.visited_nodes.path_to(&final_pos);
-            }
-            self.visited_nodes.clear();
-        }
-        unreachable!();
+    fn reset(&mut self) {
+        self.visited_nodes.clear();
+        self.stats = Default::default();
     }
 }
 
@@ -55,6 +61,21 @@ impl IdaStar {
         Self {
             visited_nodes: VisitedNodes::with_capacity(65536),
             move_board: Default::default(),
+            supplied_move_board: false,
+            stats: Default::default(),
+        }
+    }
+
+    /// Creates a new solver using the precomputed `move_board` as its heuristic.
+    ///
+    /// The move board is reused as long as the target position of the solved rounds matches the one
+    /// of `move_board`, otherwise a new one is calculated. `move_board` has to be calculated for
+    /// the same board as the solved rounds.
+    pub fn with_move_board(move_board: LeastMovesBoard) -> Self {
+        Self {
+            move_board,
+            supplied_move_board: true,
+            ..Self::new()
         }
     }
 
@@ -67,21 +88,27 @@ impl IdaStar {
         start_pos: RobotPositions,
         at_move: usize,
         max_depth: usize,
-    ) -> Option<RobotPositions> {
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Option<RobotPositions>, SolveError> {
         // Return the final position if the target has been reached.
         if max_depth == 0 {
             if round.target_reached(&start_pos) {
-                return Some(start_pos);
+                return Ok(Some(start_pos));
             }
-            return None;
+            return Ok(None);
+        }
+
+        if is_cancelled(cancel) {
+            return Err(SolveError::Cancelled);
         }
+        self.stats.expand(at_move);
 
         let calculating_move = at_move + 1;
 
-        for (pos, (robot, dir)) in start_pos.reachable_positions(round.board()) {
+        for (pos, (robot, dir)) in round.reachable_positions(&start_pos) {
             // Ignore the new positions if the target can't be reached within the limit of
             // max_depth - 1 moves.
-            if max_depth - 1 < self.move_board.min_moves(&pos, round.target()) {
+            if max_depth - 1 < round.lower_bound(&self.move_board, &pos) {
                 continue;
             }
 
@@ -100,12 +127,57 @@ impl IdaStar {
             }
 
             if let Some(final_pos) =
-                self.depth_limited_dfs(round, pos, calculating_move, max_depth - 1)
+                self.depth_limited_dfs(round, pos, calculating_move, max_depth - 1, cancel)?
             {
-                return Some(final_pos);
+                return Ok(Some(final_pos));
             }
         }
-        None
+        Ok(None)
+    }
+
+    /// Searches for a path like [`solve_cancellable`](Solver::solve_cancellable) without measuring
+    /// the elapsed time.
+    fn find_path(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Path, SolveError> {
+        self.stats = Default::default();
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("ida_star_solve", target = %round.target()).entered();
+
+        if let Some(path) = start_is_solution(round, &start_positions) {
+            return Ok(path);
+        }
+
+        if !self.supplied_move_board || self.move_board.target_position() != round.target_position()
+        {
+            self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+            self.supplied_move_board = false;
+        }
+        let start = round.lower_bound(&self.move_board, &start_positions);
+
+        if self.move_board.is_impossible_bound(start) {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        self.visited_nodes.clear();
+        for i in start.. {
+            let maybe = self.depth_limited_dfs(round, start_positions.clone(), 0, i, cancel)?;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                depth = i,
+                nodes_expanded = self.visited_nodes.len(),
+                "finished depth-limited search"
+            );
+            if let Some(final_pos) = maybe {
+                return Ok(self.visited_nodes.path_to(&final_pos));
+            }
+            self.visited_nodes.clear();
+        }
+        unreachable!();
     }
 }
 
@@ -117,26 +189,12 @@ impl Default for IdaStar {
 
 #[cfg(test)]
 mod tests {
-    use ricochet_board::{quadrant, Direction, Game, Robot, RobotPositions, Round, Symbol, Target};
+    use ricochet_board::{Direction, Robot, RobotPositions, Round, Symbol, Target};
 
+    use crate::tests::{create_board, create_round};
+    use crate::util::LeastMovesBoard;
     use crate::{IdaStar, Path, Solver};
 
-    fn create_board() -> (RobotPositions, Game) {
-        let quadrants = quadrant::gen_quadrants()
-            .iter()
-            .step_by(3)
-            .cloned()
-            .enumerate()
-            .map(|(i, mut quad)| {
-                quad.rotate_to(quadrant::ORIENTATIONS[i]);
-                quad
-            })
-            .collect::<Vec<quadrant::BoardQuadrant>>();
-
-        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
-        (pos, Game::from_quadrants(&quadrants))
-    }
-
     #[test]
     fn board_creation() {
         create_board();
@@ -161,14 +219,7 @@ mod tests {
     // Test short path
     #[test]
     fn solve() {
-        let (pos, game) = create_board();
-        let target = Target::Yellow(Symbol::Hexagon);
-
-        let round = Round::new(
-            game.board().clone(),
-            target,
-            game.get_target_position(&target).unwrap(),
-        );
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
 
         let expected = Path::new(
             pos.clone(),
@@ -188,4 +239,22 @@ mod tests {
 
         assert_eq!(IdaStar::new().solve(&round, pos), expected);
     }
+
+    #[test]
+    fn with_move_board() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let target_position = game.get_target_position(&target).unwrap();
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let move_board = LeastMovesBoard::new(game.board(), target_position);
+        let mut solver = IdaStar::with_move_board(move_board);
+        let other_pos = RobotPositions::from_tuples(&[(15, 15), (15, 0), (0, 15), (0, 0)]);
+        for start in [pos, other_pos].iter() {
+            assert_eq!(
+                solver.solve(&round, start.clone()),
+                IdaStar::new().solve(&round, start.clone())
+            );
+        }
+    }
 }
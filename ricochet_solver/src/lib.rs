@@ -1,26 +1,421 @@
 mod a_star;
 mod breadth_first;
+mod comparison;
+mod difficulty;
+mod double_move;
+mod greedy;
+mod hint;
 mod iterative_deepening;
 mod mcts;
+mod min_robots;
 pub mod util;
 
+use crate::util::{BasicVisitedNode, VisitedNodes};
+use fxhash::FxHashSet;
 use getset::Getters;
-use ricochet_board::{Direction, Robot, RobotPositions, Round};
+use rayon::prelude::*;
+use ricochet_board::{
+    Direction, Game, Position, PositionEncoding, Robot, RobotPositions, Round, StandardCondition,
+    Target, TargetCondition, ROBOTS,
+};
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 pub use a_star::AStar;
-pub use breadth_first::BreadthFirst;
+pub use breadth_first::{BreadthFirst, FrontierStats};
+pub use comparison::{compare_to_optimal, Comparison};
+pub use difficulty::{
+    classify_difficulty, count_optimal_solutions, find_board_with_length, heuristic_gap,
+    is_robot_necessary, length_histogram, random_round_with_length, redundant_walls,
+    seeds_with_target_at, Difficulty, DifficultyConfig, RANDOM_ROUND_ATTEMPTS,
+};
+pub use double_move::solve_double_move;
+pub use greedy::{greedy_upper_bound, Greedy, GREEDY_UPPER_BOUND_ATTEMPTS};
+pub use hint::{hint, Hint, HintLevel};
 pub use iterative_deepening::IdaStar;
 pub use mcts::Mcts;
+pub use min_robots::MinRobots;
 
 pub trait Solver {
     /// Find a solution to get from the `start_positions` to a target.
-    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path;
+    ///
+    /// # Panics
+    /// Panics if the solver gives up without finding a solution, see
+    /// [`SolveError::NoSolutionFound`](SolveError::NoSolutionFound).
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        match self.solve_cancellable(round, start_positions, None) {
+            Ok(path) => path,
+            Err(err) => panic!("Failed to solve the round: {}", err),
+        }
+    }
+
+    /// Find a solution to get from the `start_positions` to a target, giving up as soon as `cancel`
+    /// is set.
+    ///
+    /// The flag is polled once before each node expansion, i.e. each time the reachable positions
+    /// of a robot configuration are calculated. [`Mcts`](Mcts) polls it once per iteration of
+    /// selection, expansion, simulation and backpropagation instead, so its random rollouts delay
+    /// cancellation the most.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::Cancelled`](SolveError::Cancelled) if `cancel` has been set before a
+    /// solution was found and [`SolveError::NoSolutionFound`](SolveError::NoSolutionFound) if the
+    /// solver gave up.
+    fn solve_cancellable(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Path, SolveError>;
+
+    /// Find a solution like [`solve`](Solver::solve) and return it together with statistics of the
+    /// search.
+    fn solve_with_stats(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+    ) -> (Path, SolveStats) {
+        let path = self.solve(round, start_positions);
+        (path, self.last_stats())
+    }
+
+    /// Returns the statistics of the last search, including the time it took.
+    ///
+    /// Returns empty statistics by default, for solvers which don't collect any.
+    fn last_stats(&self) -> SolveStats {
+        SolveStats::default()
+    }
+
+    /// Returns the name of the solver as used by [`solver_by_name`](solver_by_name).
+    fn name(&self) -> &'static str;
+
+    /// Forgets everything learned in earlier searches, so the next search starts like one of a new
+    /// solver.
+    ///
+    /// Solvers keep data like the visited positions between searches to reuse the allocated
+    /// memory, which may lead to wrong results for other rounds. Does nothing by default.
+    fn reset(&mut self) {}
+
+    /// Applies the moves in `prefix` to the `start_positions` and finds a solution from the
+    /// resulting positions.
+    ///
+    /// The returned path starts at `start_positions` and its movements begin with `prefix`.
+    ///
+    /// # Panics
+    /// Panics if a move of `prefix` ends on one of the
+    /// [`forbidden_stops`](Round::forbidden_stops) of `round`.
+    fn solve_with_prefix(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        prefix: &[(Robot, Direction)],
+    ) -> Path {
+        let after_prefix =
+            prefix
+                .iter()
+                .fold(start_positions.clone(), |pos, &(robot, direction)| {
+                    replay_move(round, pos, robot, direction)
+                        .expect("A move of the prefix ends on a forbidden stop")
+                });
+        let path = self.solve(round, after_prefix);
+
+        let mut movements = prefix.to_vec();
+        movements.extend(path.movements);
+        Path::new(start_positions, path.end_pos, movements)
+    }
+}
+
+/// Solves the rounds from `rounds` one after another with the same `solver`, which is
+/// [`reset`](Solver::reset) before each search.
+///
+/// The rounds are only taken from `rounds` when the iterator is advanced, so only a single round
+/// and the memory of one solver are needed at a time.
+pub fn solve_iter<'a, S, I>(solver: &'a mut S, rounds: I) -> impl Iterator<Item = Path> + 'a
+where
+    S: Solver + ?Sized,
+    I: IntoIterator<Item = (Round, RobotPositions)>,
+    I::IntoIter: 'a,
+{
+    rounds.into_iter().map(move |(round, start_positions)| {
+        solver.reset();
+        solver.solve(&round, start_positions)
+    })
+}
+
+/// Solves every target of `game` optimally from the same `start_positions`.
+///
+/// The targets are solved in parallel, each with its own [`AStar`](AStar) solver.
+pub fn solve_board(game: &Game, start_positions: &RobotPositions) -> BTreeMap<Target, Path> {
+    game.iter_targets()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(target, position)| {
+            let round = Round::new(game.board().clone(), target, position);
+            (target, AStar::new().solve(&round, start_positions.clone()))
+        })
+        .collect()
+}
+
+/// Finds all `targets` on the board of `round` which can be reached from `start_positions` in at
+/// most `max_moves` moves, together with the length of their optimal solutions.
+///
+/// Robots are moved with [`Round::reachable_positions`](Round::reachable_positions), so the
+/// [`forbidden_stops`](Round::forbidden_stops) of `round` are respected, while its own target and
+/// condition are ignored.
+///
+/// Instead of solving each target on its own, a single breadth first search up to `max_moves`
+/// moves checks every reached position against all targets which haven't been reached yet. This
+/// is much faster than solving each target if `max_moves` is small. The targets are returned in
+/// the order of `targets`.
+pub fn targets_within(
+    round: &Round,
+    start_positions: &RobotPositions,
+    targets: &[(Target, Position)],
+    max_moves: usize,
+) -> Vec<(Target, usize)> {
+    let conditions: Vec<StandardCondition> = targets
+        .iter()
+        .map(|&(target, position)| StandardCondition::new(target, position))
+        .collect();
+    let mut lengths: Vec<Option<usize>> = vec![None; targets.len()];
+    let mut record = |positions: &RobotPositions, moves: usize| {
+        for (length, condition) in lengths.iter_mut().zip(&conditions) {
+            if length.is_none() && condition.is_satisfied(positions) {
+                *length = Some(moves);
+            }
+        }
+    };
+
+    let mut visited = FxHashSet::default();
+    visited.insert(start_positions.clone());
+    record(start_positions, 0);
+    let mut current = vec![start_positions.clone()];
+    for moves in 1..=max_moves {
+        let mut next = Vec::new();
+        for pos in &current {
+            for (new_pos, _) in round.reachable_positions(pos) {
+                if visited.insert(new_pos.clone()) {
+                    record(&new_pos, moves);
+                    next.push(new_pos);
+                }
+            }
+        }
+        current = next;
+    }
+
+    targets
+        .iter()
+        .zip(lengths)
+        .filter_map(|(&(target, _), length)| Some((target, length?)))
+        .collect()
+}
+
+/// Solves all `targets` on the board of `round` optimally from the same `start_positions` with a
+/// single search.
+///
+/// Like in [`targets_within`](targets_within), the
+/// [`forbidden_stops`](Round::forbidden_stops) of `round` are respected, while its own target and
+/// condition are ignored.
+///
+/// A breadth first search records the first position satisfying each target and stops once all
+/// targets have been reached. Since the positions visited before a target is reached are shared
+/// between all targets, this is much faster than solving each target on its own if the targets
+/// need a similar number of moves. Each path is created from the positions visited by the shared
+/// search.
+///
+/// Targets which can't be reached are left out, but finding out that a target can't be reached
+/// requires visiting every position reachable from `start_positions`.
+pub fn solve_all_targets(
+    round: &Round,
+    start_positions: &RobotPositions,
+    targets: &[(Target, Position)],
+) -> BTreeMap<Target, Path> {
+    let mut remaining: Vec<(Target, StandardCondition)> = targets
+        .iter()
+        .map(|&(target, position)| (target, StandardCondition::new(target, position)))
+        .collect();
+    let mut paths = BTreeMap::new();
+    remaining.retain(|(target, condition)| {
+        let reached = condition.is_satisfied(start_positions);
+        if reached {
+            paths.insert(*target, Path::new_start_on_target(start_positions.clone()));
+        }
+        !reached
+    });
+
+    let mut visited_nodes = VisitedNodes::with_capacity(65536);
+    let mut current = vec![start_positions.clone()];
+    let mut moves = 0;
+    while !remaining.is_empty() && !current.is_empty() {
+        moves += 1;
+        let mut next = Vec::new();
+        for pos in &current {
+            for (new_pos, movement) in round.reachable_positions(pos) {
+                if new_pos == *start_positions
+                    || visited_nodes
+                        .add_node(
+                            new_pos.clone(),
+                            pos,
+                            moves,
+                            movement,
+                            &BasicVisitedNode::new,
+                        )
+                        .was_discarded()
+                {
+                    continue;
+                }
+                remaining.retain(|(target, condition)| {
+                    let reached = condition.is_satisfied(&new_pos);
+                    if reached {
+                        paths.insert(*target, visited_nodes.path_to(&new_pos));
+                    }
+                    !reached
+                });
+                next.push(new_pos);
+            }
+        }
+        current = next;
+    }
+
+    paths
+}
+
+/// The names of all solvers which can be created with [`solver_by_name`](solver_by_name).
+pub const SOLVER_NAMES: [&str; 5] = [
+    "breadth-first",
+    "a-star",
+    "ida-star",
+    "greedy",
+    "min-robots",
+];
+
+/// Creates the solver called `name` with its default configuration.
+///
+/// Returns `None` if there is no solver with this name. [`Mcts`](Mcts) isn't available here, since
+/// it needs to be given the time it may spend on each move.
+pub fn solver_by_name(name: &str) -> Option<Box<dyn Solver>> {
+    match name {
+        "breadth-first" => Some(Box::new(BreadthFirst::new())),
+        "a-star" => Some(Box::new(AStar::new())),
+        "ida-star" => Some(Box::new(IdaStar::new())),
+        "greedy" => Some(Box::new(Greedy::new())),
+        "min-robots" => Some(Box::new(MinRobots::new())),
+        _ => None,
+    }
+}
+
+/// Moves `robot` in `direction` according to the rules of `round`.
+///
+/// Unlike [`Round::move_robot`](Round::move_robot), a move which doesn't change the positions is
+/// allowed and keeps them as they are. Returns `None` if the move ends on a forbidden stop.
+pub(crate) fn replay_move(
+    round: &Round,
+    positions: RobotPositions,
+    robot: Robot,
+    direction: Direction,
+) -> Option<RobotPositions> {
+    match round.move_robot(&positions, robot, direction) {
+        Some(moved) => Some(moved),
+        None if positions
+            .clone()
+            .move_in_direction(round.board(), robot, direction)
+            == positions =>
+        {
+            Some(positions)
+        }
+        None => None,
+    }
+}
+
+/// Returns a path without any moves if the target of `round` is already reached at the
+/// `start_positions`.
+///
+/// This is a cheap check all solvers perform before searching, which callers can use before
+/// choosing a solver.
+pub fn start_is_solution(round: &Round, start_positions: &RobotPositions) -> Option<Path> {
+    if round.target_reached(start_positions) {
+        Some(Path::new_start_on_target(start_positions.clone()))
+    } else {
+        None
+    }
+}
+
+/// Statistics collected by a solver while searching for a solution.
+///
+/// A node is expanded each time the reachable positions of a robot configuration are calculated,
+/// its depth is the number of moves needed to reach it from the starting positions. [`Mcts`](Mcts)
+/// counts each iteration of selection, expansion, simulation and backpropagation as one expanded
+/// node instead, using the depth of the selected leaf.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    elapsed: Duration,
+    nodes_expanded: usize,
+    max_depth_reached: usize,
+}
+
+impl SolveStats {
+    /// Returns the time spent searching for the solution.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of nodes which have been expanded.
+    pub fn nodes_expanded(&self) -> usize {
+        self.nodes_expanded
+    }
+
+    /// Returns the greatest depth of an expanded node.
+    pub fn max_depth_reached(&self) -> usize {
+        self.max_depth_reached
+    }
+
+    /// Records the expansion of a node at `depth`.
+    fn expand(&mut self, depth: usize) {
+        self.nodes_expanded += 1;
+        self.max_depth_reached = self.max_depth_reached.max(depth);
+    }
+}
+
+/// The reasons a solver may stop without finding a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The cancellation flag has been set while solving.
+    Cancelled,
+    /// The solver used up its budget without finding a solution, only returned by solvers which
+    /// may miss existing solutions like [`Greedy`](Greedy).
+    NoSolutionFound,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Cancelled => write!(f, "the solver has been cancelled"),
+            SolveError::NoSolutionFound => write!(f, "the solver gave up without a solution"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Checks if the optional cancellation flag has been set.
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|flag| flag.load(Ordering::Relaxed))
 }
 
 /// A path from a starting position to another position.
 ///
 /// Contains the starting positions of the robots, their final positions and a path from the former
 /// to the latter. The path consists of tuples of a robot and the direction it moved in.
+///
+/// Paths are ordered by their number of moves first and the number of distinct robots moved
+/// second. Ties are broken by the movements and then the starting and final positions, so only
+/// equal paths are ordered as [`Equal`](std::cmp::Ordering::Equal).
 #[derive(Debug, Clone, PartialEq, Eq, Getters)]
 #[getset(get = "pub")]
 pub struct Path {
@@ -59,4 +454,808 @@ impl Path {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns the first move of the path or `None` if the path is empty.
+    ///
+    /// The first move can be used as a hint without revealing the whole path.
+    pub fn first_move(&self) -> Option<(Robot, Direction)> {
+        self.movements.first().copied()
+    }
+
+    /// Returns the number of distinct robots moved in the path.
+    pub fn robot_count(&self) -> usize {
+        ROBOTS
+            .iter()
+            .filter(|&&robot| self.movements.iter().any(|&(moved, _)| moved == robot))
+            .count()
+    }
+
+    /// Returns the robot reaching the target of `round` at the end of the path.
+    ///
+    /// For colored targets this is always the robot of the same color. The spiral can be reached by
+    /// any robot, so the robot on the target position is returned.
+    ///
+    /// # Panics
+    /// Panics if the target is the spiral and no robot ends on its position.
+    pub fn target_robot(&self, round: &Round) -> Robot {
+        Robot::try_from(round.target()).unwrap_or_else(|_| {
+            *ROBOTS
+                .iter()
+                .find(|&&robot| self.end_pos[robot] == round.target_position())
+                .expect("No robot ends on the target")
+        })
+    }
+
+    /// Returns the path after rotating a board with `side_length` clockwise by a quarter turn.
+    ///
+    /// The rotated path solves the round returned by
+    /// [`Round::rotate_clockwise`](ricochet_board::Round::rotate_clockwise).
+    pub fn rotate_clockwise(&self, side_length: PositionEncoding) -> Self {
+        Self::new(
+            self.start_pos.rotate_clockwise(side_length),
+            self.end_pos.rotate_clockwise(side_length),
+            self.movements
+                .iter()
+                .map(|&(robot, direction)| (robot, direction.rotate_clockwise()))
+                .collect(),
+        )
+    }
+
+    /// Replays the movements according to the rules of `round` and returns the positions of the
+    /// robots after each move.
+    ///
+    /// The first element is the starting position and the last one is the final position.
+    ///
+    /// # Panics
+    /// Panics if a move ends on one of the [`forbidden_stops`](Round::forbidden_stops) of `round`.
+    pub fn positions(&self, round: &Round) -> Vec<RobotPositions> {
+        let mut positions = Vec::with_capacity(self.len() + 1);
+        positions.push(self.start_pos.clone());
+        for &(robot, direction) in &self.movements {
+            let current = positions.last().unwrap().clone();
+            positions.push(
+                replay_move(round, current, robot, direction)
+                    .expect("A move of the path ends on a forbidden stop"),
+            );
+        }
+        positions
+    }
+
+    /// Returns the upper left and bottom right corner of the smallest rectangle containing all
+    /// robots in every position of the path on `round`.
+    ///
+    /// # Panics
+    /// Panics if a move ends on one of the [`forbidden_stops`](Round::forbidden_stops) of `round`.
+    pub fn bounding_box(&self, round: &Round) -> (Position, Position) {
+        let fields: Vec<Position> = self
+            .positions(round)
+            .iter()
+            .flat_map(|positions| positions.to_array())
+            .collect();
+        let columns = fields.iter().map(|pos| pos.column());
+        let rows = fields.iter().map(|pos| pos.row());
+        (
+            Position::new(columns.clone().min().unwrap(), rows.clone().min().unwrap()),
+            Position::new(columns.max().unwrap(), rows.max().unwrap()),
+        )
+    }
+
+    /// Returns the path without moves that aren't needed to reach the target of `round`.
+    ///
+    /// First, every longest sequence of moves after which all robots are back on the positions
+    /// they had before the sequence is removed. Afterwards single moves are dropped as long as the
+    /// remaining moves still reach the target. Removing a move changes where later moves stop, so
+    /// the path is replayed after each removal. The result is not necessarily optimal.
+    ///
+    /// # Panics
+    /// Panics if the path doesn't reach the target of `round` or a move ends on one of its
+    /// [`forbidden_stops`](Round::forbidden_stops).
+    pub fn simplify(&self, round: &Round) -> Self {
+        assert!(
+            round.target_reached(&self.end_pos),
+            "The path doesn't reach the target"
+        );
+
+        let mut movements = Vec::with_capacity(self.len());
+        let positions = self.positions(round);
+        let mut i = 0;
+        while i < self.len() {
+            // Skip to the last occurrence of the current positions.
+            i = positions
+                .iter()
+                .rposition(|pos| *pos == positions[i])
+                .unwrap();
+            if let Some(&movement) = self.movements.get(i) {
+                movements.push(movement);
+            }
+            i += 1;
+        }
+
+        let replay = |movements: &[(Robot, Direction)]| {
+            movements
+                .iter()
+                .try_fold(self.start_pos.clone(), |pos, &(robot, direction)| {
+                    replay_move(round, pos, robot, direction)
+                })
+        };
+        let mut index = 0;
+        while index < movements.len() {
+            let mut shortened = movements.clone();
+            shortened.remove(index);
+            if matches!(replay(&shortened), Some(end_pos) if round.target_reached(&end_pos)) {
+                movements = shortened;
+                index = 0;
+            } else {
+                index += 1;
+            }
+        }
+
+        let end_pos = replay(&movements).expect("A move of the path ends on a forbidden stop");
+        Self::new(self.start_pos.clone(), end_pos, movements)
+    }
+
+    /// Checks if the path is a solution of `round`.
+    ///
+    /// The movements are replayed from the starting positions, which have to end on the final
+    /// positions of the path with the target of `round` reached. No move may end on one of the
+    /// [`forbidden_stops`](Round::forbidden_stops) of `round`.
+    pub fn verify(&self, round: &Round) -> bool {
+        let end_pos = self
+            .movements
+            .iter()
+            .try_fold(self.start_pos.clone(), |pos, &(robot, direction)| {
+                replay_move(round, pos, robot, direction)
+            });
+        end_pos.as_ref() == Some(&self.end_pos) && round.target_reached(&self.end_pos)
+    }
+
+    /// Strictly checks if the path is a solution of `round` starting from `start_positions`.
+    ///
+    /// Unlike [`verify`](Path::verify), the path also has to start on `start_positions` and every
+    /// move has to be allowed by [`Round::move_robot`](Round::move_robot), so moves which don't
+    /// change the positions or end on a forbidden field are rejected.
+    pub fn matches_round(&self, round: &Round, start_positions: &RobotPositions) -> bool {
+        if self.start_pos != *start_positions {
+            return false;
+        }
+        let end_pos = self
+            .movements
+            .iter()
+            .try_fold(self.start_pos.clone(), |pos, &(robot, direction)| {
+                round.move_robot(&pos, robot, direction)
+            });
+        end_pos.as_ref() == Some(&self.end_pos) && round.target_reached(&self.end_pos)
+    }
+
+    /// Returns the movements in reverse order, each with the field the robot started the move on.
+    ///
+    /// Walking through the result undoes the path, starting at the final positions: the robot of
+    /// each entry is moved back to the contained field.
+    ///
+    /// # Panics
+    /// Panics if a move ends on one of the [`forbidden_stops`](Round::forbidden_stops) of `round`.
+    pub fn reversed(&self, round: &Round) -> Vec<(Robot, Direction, Position)> {
+        self.positions(round)
+            .iter()
+            .zip(&self.movements)
+            .map(|(before, &(robot, direction))| (robot, direction, before[robot]))
+            .rev()
+            .collect()
+    }
+
+    /// Replays the movements according to the rules of `round` and annotates each move with the
+    /// reason the robot stopped.
+    ///
+    /// # Panics
+    /// Panics if a move ends on one of the [`forbidden_stops`](Round::forbidden_stops) of `round`.
+    pub fn annotate(&self, round: &Round) -> Vec<(Robot, Direction, StopReason)> {
+        let board = round.board();
+        let side_length = board.side_length();
+        self.positions(round)
+            .iter()
+            .skip(1)
+            .zip(&self.movements)
+            .map(|(positions, &(robot, direction))| {
+                let stop = positions[robot];
+                let next = stop.to_direction(direction, side_length);
+                let reason = if board.is_adjacent_to_wall(stop, direction) {
+                    let on_edge = match direction {
+                        Direction::Up => stop.row() == 0,
+                        Direction::Down => stop.row() == side_length - 1,
+                        Direction::Left => stop.column() == 0,
+                        Direction::Right => stop.column() == side_length - 1,
+                    };
+                    if on_edge {
+                        StopReason::BoardEdge
+                    } else {
+                        StopReason::Wall
+                    }
+                } else if board.is_obstacle(next) {
+                    StopReason::Obstacle
+                } else if let Some(&blocking) =
+                    ROBOTS.iter().find(|&&other| positions[other] == next)
+                {
+                    StopReason::Robot(blocking)
+                } else {
+                    assert!(
+                        round.forbidden_stops().contains(&next),
+                        "A robot stopped without being blocked"
+                    );
+                    StopReason::ForbiddenStop
+                };
+                (robot, direction, reason)
+            })
+            .collect()
+    }
+}
+
+/// The reasons a robot stops at the end of a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The robot hit a wall on the board.
+    Wall,
+    /// The robot hit the wall at the edge of the board.
+    BoardEdge,
+    /// The robot hit an obstacle.
+    Obstacle,
+    /// The robot hit the contained robot.
+    Robot(Robot),
+    /// The robot stopped in front of fields it may not stop on, see
+    /// [`ForbiddenStopRule::PassThrough`](ricochet_board::ForbiddenStopRule::PassThrough).
+    ForbiddenStop,
+}
+
+impl PartialOrd for Path {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Path {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        (self.len(), self.robot_count())
+            .cmp(&(other.len(), other.robot_count()))
+            .then_with(|| self.movements.cmp(&other.movements))
+            .then_with(|| self.start_pos.to_array().cmp(&other.start_pos.to_array()))
+            .then_with(|| self.end_pos.to_array().cmp(&other.end_pos.to_array()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{
+        quadrant, Board, Direction, ForbiddenStopRule, Game, Position, Robot, RobotPositions,
+        Round, Symbol, Target,
+    };
+    use std::cmp::Ordering;
+    use std::collections::{BTreeSet, HashSet};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::{
+        solve_board, solve_iter, solver_by_name, start_is_solution, targets_within, AStar,
+        BreadthFirst, Greedy, IdaStar, Mcts, MinRobots, Path, SolveError, Solver, StopReason,
+        SOLVER_NAMES,
+    };
+
+    /// Creates the game made up of every third quadrant, which is used by the tests of most
+    /// modules, together with the starting positions of the robots.
+    pub(crate) fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    /// Creates a round on the board of `game` with `target` on the position it has in `game`.
+    pub(crate) fn round_on(game: &Game, target: Target) -> Round {
+        Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        )
+    }
+
+    /// Creates a round for `target` on the game returned by [`create_board`], together with the
+    /// starting positions of the robots.
+    pub(crate) fn create_round(target: Target) -> (RobotPositions, Round) {
+        let (pos, game) = create_board();
+        (pos, round_on(&game, target))
+    }
+
+    #[test]
+    fn path_positions() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let path = BreadthFirst::new().solve(&round, pos.clone());
+        let positions = path.positions(&round);
+
+        assert_eq!(positions.len(), path.len() + 1);
+        assert_eq!(positions.first(), Some(&pos));
+        assert_eq!(positions.last(), Some(path.end_pos()));
+        for (step, &(robot, _)) in positions.windows(2).zip(path.movements()) {
+            assert_eq!(step[0].differing_robots(&step[1]), vec![robot]);
+        }
+    }
+
+    #[test]
+    fn path_bounding_box() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        assert_eq!(
+            Path::new_start_on_target(pos.clone()).bounding_box(&round),
+            (Position::new(0, 1), Position::new(7, 15))
+        );
+
+        let moved = pos
+            .clone()
+            .move_in_direction(round.board(), Robot::Yellow, Direction::Right);
+        let path = Path::new(pos, moved, vec![(Robot::Yellow, Direction::Right)]);
+        assert_eq!(
+            path.bounding_box(&round),
+            (Position::new(0, 1), Position::new(13, 15))
+        );
+    }
+
+    #[test]
+    fn cancelled_solve() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let cancel = Arc::new(AtomicBool::new(true));
+        let mut solvers: Vec<Box<dyn Solver>> = vec![
+            Box::new(BreadthFirst::new()),
+            Box::new(AStar::new()),
+            Box::new(Greedy::new_seeded(0)),
+            Box::new(IdaStar::new()),
+            Box::new(MinRobots::new()),
+            Box::new(Mcts::new_seeded(chrono::Duration::seconds(1), 0)),
+        ];
+        for solver in solvers.iter_mut() {
+            let result = solver.solve_cancellable(&round, pos.clone(), Some(cancel.clone()));
+            assert_eq!(result, Err(SolveError::Cancelled));
+        }
+    }
+
+    #[test]
+    fn uncancelled_solve() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let path = AStar::new().solve_cancellable(&round, pos.clone(), Some(cancel));
+        assert_eq!(
+            path.map(|path| path.len()),
+            Ok(BreadthFirst::new().solve(&round, pos).len())
+        );
+    }
+
+    #[test]
+    fn path_ordering() {
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        let path =
+            |movements: Vec<(Robot, Direction)>| Path::new(pos.clone(), pos.clone(), movements);
+        let one_robot = path(vec![
+            (Robot::Red, Direction::Up),
+            (Robot::Red, Direction::Left),
+        ]);
+        let two_robots = path(vec![
+            (Robot::Red, Direction::Up),
+            (Robot::Blue, Direction::Left),
+        ]);
+        let other_robot = path(vec![
+            (Robot::Green, Direction::Down),
+            (Robot::Green, Direction::Right),
+        ]);
+        let longer = path(vec![(Robot::Red, Direction::Up); 3]);
+
+        assert_eq!(one_robot.robot_count(), 1);
+        assert_eq!(two_robots.robot_count(), 2);
+        assert!(one_robot < two_robots);
+        assert!(two_robots < longer);
+        // Paths of the same length moving the same number of robots are ordered by their moves.
+        assert!(one_robot < other_robot);
+        assert_eq!(one_robot.cmp(&one_robot.clone()), Ordering::Equal);
+        let end = RobotPositions::from_tuples(&[(0, 0), (5, 4), (7, 1), (7, 15)]);
+        let other_end = Path::new(pos.clone(), end, one_robot.movements().clone());
+        assert_ne!(one_robot.cmp(&other_end), Ordering::Equal);
+        let set: BTreeSet<_> = vec![one_robot.clone(), other_robot.clone(), other_end]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 3);
+
+        let mut paths = vec![longer.clone(), two_robots.clone(), one_robot.clone()];
+        paths.sort();
+        assert_eq!(paths, vec![one_robot, two_robots, longer]);
+    }
+
+    #[test]
+    fn solve_with_prefix() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let prefix = [
+            (Robot::Green, Direction::Down),
+            (Robot::Red, Direction::Right),
+        ];
+        let path = AStar::new().solve_with_prefix(&round, pos.clone(), &prefix);
+
+        assert_eq!(path.start_pos(), &pos);
+        assert_eq!(&path.movements()[..prefix.len()], &prefix);
+        assert!(round.target_reached(path.end_pos()));
+        assert_eq!(path.positions(&round).last(), Some(path.end_pos()));
+    }
+
+    #[test]
+    fn target_robot() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let path = AStar::new().solve(&round, pos.clone());
+        assert_eq!(path.target_robot(&round), Robot::Yellow);
+
+        let spiral = Round::new(
+            round.board().clone(),
+            Target::Spiral,
+            pos.clone()
+                .move_in_direction(round.board(), Robot::Green, Direction::Down)[Robot::Green],
+        );
+        let path = AStar::new().solve(&spiral, pos);
+        assert_eq!(path.len(), 1);
+        assert_eq!(path.target_robot(&spiral), Robot::Green);
+    }
+
+    #[test]
+    fn rotate_path() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let path = AStar::new().solve(&round, pos);
+        let side_length = round.board().side_length();
+        let rotated_round = round.rotate_clockwise();
+        let rotated = path.rotate_clockwise(side_length);
+
+        assert_eq!(rotated.len(), path.len());
+        assert_eq!(
+            rotated.positions(&rotated_round).last(),
+            Some(rotated.end_pos())
+        );
+        assert!(rotated_round.target_reached(rotated.end_pos()));
+        assert_eq!(
+            AStar::new()
+                .solve(&rotated_round, rotated.start_pos().clone())
+                .len(),
+            path.len()
+        );
+    }
+
+    #[test]
+    fn reversed_path() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let start = RobotPositions::from_tuples(&[(0, 0), (0, 5), (0, 6), (5, 6)]);
+        let movements = vec![
+            (Robot::Red, Direction::Right),
+            (Robot::Red, Direction::Down),
+            (Robot::Yellow, Direction::Left),
+        ];
+        let end = movements
+            .iter()
+            .fold(start.clone(), |pos, &(robot, direction)| {
+                pos.move_in_direction(&board, robot, direction)
+            });
+        let path = Path::new(start, end, movements);
+        let round = Round::new(board, Target::Spiral, Position::new(15, 15));
+
+        assert_eq!(
+            path.reversed(&round),
+            vec![
+                (Robot::Yellow, Direction::Left, Position::new(5, 6)),
+                (Robot::Red, Direction::Down, Position::new(15, 0)),
+                (Robot::Red, Direction::Right, Position::new(0, 0)),
+            ]
+        );
+        assert!(Path::new_start_on_target(path.start_pos().clone())
+            .reversed(&round)
+            .is_empty());
+    }
+
+    #[test]
+    fn annotate_path() {
+        let board = Board::new_empty(16)
+            .wall_enclosure()
+            .set_vertical_line(3, 5, 1)
+            .set_obstacles(vec![Position::new(10, 0)]);
+        let start = RobotPositions::from_tuples(&[(0, 0), (0, 5), (0, 6), (5, 6)]);
+        let movements = vec![
+            (Robot::Red, Direction::Right),
+            (Robot::Blue, Direction::Right),
+            (Robot::Green, Direction::Right),
+            (Robot::Yellow, Direction::Down),
+        ];
+        let end = movements
+            .iter()
+            .fold(start.clone(), |pos, &(robot, direction)| {
+                pos.move_in_direction(&board, robot, direction)
+            });
+        let path = Path::new(start, end, movements);
+        let round = Round::new(board, Target::Spiral, Position::new(15, 15));
+
+        assert_eq!(
+            path.annotate(&round),
+            vec![
+                (Robot::Red, Direction::Right, StopReason::Obstacle),
+                (Robot::Blue, Direction::Right, StopReason::Wall),
+                (
+                    Robot::Green,
+                    Direction::Right,
+                    StopReason::Robot(Robot::Yellow)
+                ),
+                (Robot::Yellow, Direction::Down, StopReason::BoardEdge),
+            ]
+        );
+    }
+
+    #[test]
+    fn path_helpers_respect_pass_through() {
+        let forbidden: HashSet<_> = vec![Position::new(15, 0)].into_iter().collect();
+        let round = Round::new(
+            Board::new_empty(16).wall_enclosure(),
+            Target::Red(Symbol::Square),
+            Position::new(14, 0),
+        )
+        .with_forbidden_stops(forbidden, ForbiddenStopRule::PassThrough);
+        let start = RobotPositions::from_tuples(&[(0, 0), (0, 5), (0, 6), (5, 6)]);
+        let path = BreadthFirst::new().solve(&round, start.clone());
+        assert_eq!(path.movements(), &vec![(Robot::Red, Direction::Right)]);
+
+        assert_eq!(path.positions(&round).last(), Some(path.end_pos()));
+        assert_eq!(
+            path.annotate(&round),
+            vec![(Robot::Red, Direction::Right, StopReason::ForbiddenStop)]
+        );
+        assert_eq!(
+            path.reversed(&round),
+            vec![(Robot::Red, Direction::Right, Position::new(0, 0))]
+        );
+        assert_eq!(
+            path.bounding_box(&round),
+            (Position::new(0, 0), Position::new(14, 6))
+        );
+
+        let looped = Path::new(
+            start,
+            path.end_pos().clone(),
+            vec![
+                (Robot::Red, Direction::Right),
+                (Robot::Red, Direction::Left),
+                (Robot::Red, Direction::Right),
+            ],
+        );
+        assert!(looped.verify(&round));
+        assert_eq!(looped.simplify(&round), path);
+    }
+
+    #[test]
+    fn simplify_path() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let path = AStar::new().solve(&round, pos.clone());
+        assert_eq!(path.simplify(&round), path);
+
+        // Repeating a move doesn't move the robot again.
+        let mut movements = path.movements().clone();
+        movements.insert(0, movements[0]);
+        movements.insert(0, movements[0]);
+        let padded = Path::new(pos, path.end_pos().clone(), movements);
+        assert_eq!(padded.positions(&round).last(), Some(path.end_pos()));
+        assert_eq!(padded.simplify(&round), path);
+    }
+
+    #[test]
+    fn verify_path() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let path = AStar::new().solve(&round, pos.clone());
+        assert!(path.verify(&round));
+
+        let mut movements = path.movements().clone();
+        movements.pop();
+        let shortened = Path::new(pos.clone(), path.end_pos().clone(), movements);
+        assert!(!shortened.verify(&round));
+        assert!(!Path::new_start_on_target(pos).verify(&round));
+    }
+
+    #[test]
+    fn solvers_respect_forbidden_stops() {
+        let (round, pos) = ricochet_board::round!(
+            Target::Red(Symbol::Square),
+            "
+            R . . . .
+            . . . . .
+            . . . . *
+            . . . . .
+            B G . . Y
+            "
+        );
+        let path = BreadthFirst::new().solve(&round, pos.clone());
+
+        // Forbid the field the first move of the optimal path stops on.
+        let (robot, _) = path.movements()[0];
+        let forbidden: HashSet<_> = vec![path.positions(&round)[1][robot]].into_iter().collect();
+
+        for &rule in [
+            ForbiddenStopRule::Disallowed,
+            ForbiddenStopRule::PassThrough,
+        ]
+        .iter()
+        {
+            let restricted = round.clone().with_forbidden_stops(forbidden.clone(), rule);
+            if rule == ForbiddenStopRule::Disallowed {
+                assert!(!path.verify(&restricted));
+            }
+            let optimal = BreadthFirst::new().solve(&restricted, pos.clone());
+            assert!(optimal.matches_round(&restricted, &pos));
+
+            let mut solvers: Vec<Box<dyn Solver>> = vec![
+                Box::new(AStar::new()),
+                Box::new(AStar::with_move_table()),
+                Box::new(IdaStar::new()),
+                Box::new(MinRobots::new()),
+            ];
+            for solver in &mut solvers {
+                let solver_path = solver.solve(&restricted, pos.clone());
+                assert!(solver_path.matches_round(&restricted, &pos));
+                assert!(solver_path.verify(&restricted));
+                if solver.name() != "min-robots" {
+                    assert_eq!(solver_path.len(), optimal.len());
+                }
+            }
+
+            let target = (restricted.target(), restricted.target_position());
+            let paths = crate::solve_all_targets(&restricted, &pos, &[target]);
+            assert!(paths[&target.0].matches_round(&restricted, &pos));
+            assert_eq!(paths[&target.0].len(), optimal.len());
+        }
+    }
+
+    #[test]
+    fn path_matches_round() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let path = AStar::new().solve(&round, pos.clone());
+        assert!(path.matches_round(&round, &pos));
+        assert!(!path.matches_round(&round, path.end_pos()));
+
+        // A repeated move is replayed fine, but doesn't move the robot.
+        let mut movements = path.movements().clone();
+        movements.insert(0, movements[0]);
+        let padded = Path::new(pos.clone(), path.end_pos().clone(), movements);
+        assert!(padded.verify(&round));
+        assert!(!padded.matches_round(&round, &pos));
+    }
+
+    #[test]
+    fn solve_stats() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let (path, stats) = BreadthFirst::new().solve_with_stats(&round, pos.clone());
+        assert!(stats.nodes_expanded() > 0);
+        assert_eq!(stats.max_depth_reached(), path.len() - 1);
+
+        let mut solver = AStar::new();
+        let (_, stats) = solver.solve_with_stats(&round, pos.clone());
+        assert!(stats.nodes_expanded() > 0);
+        assert!(stats.elapsed() > Duration::ZERO);
+        assert_eq!(solver.last_stats(), stats);
+
+        // The solvers measure the time themselves.
+        solver.solve(&round, pos.clone());
+        assert!(solver.last_stats().elapsed() > Duration::ZERO);
+
+        let on_target = path.end_pos().clone();
+        let (_, stats) = solver.solve_with_stats(&round, on_target);
+        assert_eq!(stats.nodes_expanded(), 0);
+    }
+
+    #[test]
+    fn solver_registry() {
+        for &name in SOLVER_NAMES.iter() {
+            let solver = solver_by_name(name).unwrap();
+            assert_eq!(solver.name(), name);
+        }
+        assert!(solver_by_name("unknown").is_none());
+    }
+
+    #[test]
+    fn solve_all_targets() {
+        let (pos, _) = create_round(Target::Yellow(Symbol::Hexagon));
+        let game = quadrant::game_from_seed(3);
+        let paths = solve_board(&game, &pos);
+
+        assert_eq!(paths.len(), game.target_count());
+        for (target, position) in game.iter_targets() {
+            let round = Round::new(game.board().clone(), target, position);
+            let path = &paths[&target];
+            assert!(path.verify(&round));
+            assert_eq!(
+                path.len(),
+                BreadthFirst::new().solve(&round, pos.clone()).len()
+            );
+        }
+    }
+
+    #[test]
+    fn targets_within_moves() {
+        let (pos, _) = create_round(Target::Yellow(Symbol::Hexagon));
+        let game = quadrant::game_from_seed(3);
+        let targets = game.iter_targets().collect::<Vec<_>>();
+        let round = Round::new(game.board().clone(), targets[0].0, targets[0].1);
+        let paths = solve_board(&game, &pos);
+
+        let within = targets_within(&round, &pos, &targets, 5);
+        let expected = targets
+            .iter()
+            .map(|(target, _)| (*target, paths[target].len()))
+            .filter(|&(_, length)| length <= 5)
+            .collect::<Vec<_>>();
+        assert!(!within.is_empty());
+        assert_eq!(within, expected);
+        assert!(targets_within(&round, &pos, &targets, 0).is_empty());
+    }
+
+    #[test]
+    fn all_targets_in_one_search() {
+        let (pos, _) = create_round(Target::Yellow(Symbol::Hexagon));
+        let game = quadrant::game_from_seed(3);
+        let targets = game.iter_targets().collect::<Vec<_>>();
+        let round = Round::new(game.board().clone(), targets[0].0, targets[0].1);
+        let expected = solve_board(&game, &pos);
+
+        let paths = crate::solve_all_targets(&round, &pos, &targets);
+        assert_eq!(paths.len(), expected.len());
+        for (target, position) in targets {
+            let round = Round::new(game.board().clone(), target, position);
+            assert!(paths[&target].matches_round(&round, &pos));
+            assert_eq!(paths[&target].len(), expected[&target].len());
+        }
+    }
+
+    #[test]
+    fn reused_solver() {
+        let (pos, _) = create_round(Target::Yellow(Symbol::Hexagon));
+        let rounds: Vec<_> = (0..6)
+            .map(|seed| (quadrant::round_from_seed(seed * 100), pos.clone()))
+            .collect();
+
+        for name in ["breadth-first", "a-star", "ida-star"].iter() {
+            let mut solver = solver_by_name(name).unwrap();
+            let paths: Vec<_> = solve_iter(&mut *solver, rounds.clone()).collect();
+            for (path, (round, start)) in paths.iter().zip(&rounds) {
+                let fresh = solver_by_name(name).unwrap().solve(round, start.clone());
+                assert!(path.verify(round));
+                assert_eq!(path.len(), fresh.len());
+            }
+        }
+    }
+
+    #[test]
+    fn a_star_with_move_table() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        let path = AStar::with_move_table().solve(&round, pos.clone());
+        assert!(round.target_reached(path.end_pos()));
+        assert_eq!(path.len(), AStar::new().solve(&round, pos).len());
+    }
+
+    #[test]
+    fn start_on_target() {
+        let (pos, round) = create_round(Target::Yellow(Symbol::Hexagon));
+        assert_eq!(start_is_solution(&round, &pos), None);
+
+        let colored = Round::new(round.board().clone(), round.target(), pos[Robot::Yellow]);
+        assert_eq!(
+            start_is_solution(&colored, &pos),
+            Some(Path::new_start_on_target(pos.clone()))
+        );
+        let wrong_color = Round::new(round.board().clone(), round.target(), pos[Robot::Red]);
+        assert_eq!(start_is_solution(&wrong_color, &pos), None);
+
+        let spiral = Round::new(round.board().clone(), Target::Spiral, pos[Robot::Red]);
+        let path = start_is_solution(&spiral, &pos).unwrap();
+        assert!(path.is_empty());
+        assert_eq!(path.end_pos(), &pos);
+    }
 }
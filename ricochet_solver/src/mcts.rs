@@ -6,8 +6,11 @@ use indexmap::IndexSet;
 use rand::SeedableRng;
 use ricochet_board::{Direction, Robot, RobotPositions, Round};
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
 
-use crate::{Path, Solver};
+use crate::{is_cancelled, Path, SolveError, SolveStats, Solver};
 
 type NodeMap = HashMap<RobotPositions, NodeData, FxBuildHasher>;
 
@@ -34,7 +37,7 @@ impl NodeData {
         if round.target_reached(&self.position) {
             Vec::new()
         } else {
-            self.position.reachable_positions(round.board()).collect()
+            round.reachable_positions(&self.position).collect()
         }
     }
 
@@ -58,6 +61,7 @@ pub struct Mcts {
     num_rollouts: usize,
     nodes: NodeMap,
     seed: u64,
+    stats: SolveStats,
 }
 
 impl Mcts {
@@ -74,6 +78,7 @@ impl Mcts {
             num_rollouts: 5,
             nodes: HashMap::with_capacity_and_hasher(65536, Default::default()),
             seed,
+            stats: Default::default(),
         }
     }
 
@@ -153,8 +158,8 @@ impl Mcts {
         let mut moves = 0;
         let mut current_pos = from.clone();
         while !round.target_reached(&current_pos) {
-            let mut reachable = current_pos
-                .reachable_positions(round.board())
+            let mut reachable = round
+                .reachable_positions(&current_pos)
                 .map(|(pos, _)| pos)
                 .collect::<Vec<_>>();
             current_pos = reachable.swap_remove(rng.gen_range(0..reachable.len()));
@@ -173,8 +178,16 @@ impl Mcts {
     }
 
     /// Perform selection, expansion, simulation and backpropagation once.
-    fn run(&mut self, current_root: &RobotPositions, round: &Round, rng: &mut impl rand::Rng) {
+    ///
+    /// Returns the number of moves from `current_root` to the selected leaf.
+    fn run(
+        &mut self,
+        current_root: &RobotPositions,
+        round: &Round,
+        rng: &mut impl rand::Rng,
+    ) -> usize {
         let leaf_path = self.selection(current_root, round);
+        let leaf_depth = leaf_path.len() - 1;
         let leaf = leaf_path.last().unwrap().clone();
         self.expansion(&leaf);
         let mut length = u64::MAX;
@@ -182,6 +195,7 @@ impl Mcts {
             length = length.min(self.simulation(&leaf, round, rng));
         }
         self.backpropagation(leaf_path, length);
+        leaf_depth
     }
 
     /// Calculates the uct score of a node using the negative mean score, since lower scores are
@@ -195,10 +209,16 @@ impl Mcts {
                 * node_data.mean_score()
                 * f64::sqrt(f64::ln(parent_visits as f64) / node_data.visits as f64)
     }
-}
 
-impl Solver for Mcts {
-    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+    /// Searches for a path like [`solve_cancellable`](Solver::solve_cancellable) without measuring
+    /// the elapsed time.
+    fn find_path(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Path, SolveError> {
+        self.stats = Default::default();
         let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
         let mut current_pos = start_positions.clone();
         let mut movements = Vec::new();
@@ -207,7 +227,11 @@ impl Solver for Mcts {
             let move_start = Local::now();
 
             while Local::now() - move_start <= self.time_per_move {
-                self.run(&current_pos, round, &mut rng);
+                if is_cancelled(cancel) {
+                    return Err(SolveError::Cancelled);
+                }
+                let leaf_depth = self.run(&current_pos, round, &mut rng);
+                self.stats.expand(movements.len() + leaf_depth);
             }
 
             let (new_pos, movement) = self.choose_best_child(&current_pos, round, &mut rng);
@@ -215,7 +239,34 @@ impl Solver for Mcts {
             current_pos = new_pos;
         }
 
-        Path::new(start_positions, current_pos, movements)
+        Ok(Path::new(start_positions, current_pos, movements))
+    }
+}
+
+impl Solver for Mcts {
+    fn solve_cancellable(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Path, SolveError> {
+        let started = Instant::now();
+        let result = self.find_path(round, start_positions, cancel.as_deref());
+        self.stats.elapsed = started.elapsed();
+        result
+    }
+
+    fn last_stats(&self) -> SolveStats {
+        self.stats
+    }
+
+    fn name(&self) -> &'static str {
+        "mcts"
+    }
+
+    fn reset(&mut self) {
+        self.nodes.clear();
+        self.stats = Default::default();
     }
 }
 
@@ -224,24 +275,9 @@ mod tests {
     use rand::{Rng, SeedableRng};
     use ricochet_board::*;
 
+    use crate::tests::{create_board, create_round};
     use crate::{Mcts, Path, Solver};
 
-    fn create_board() -> (RobotPositions, Game) {
-        let quadrants = quadrant::gen_quadrants()
-            .iter()
-            .step_by(3)
-            .cloned()
-            .enumerate()
-            .map(|(i, mut quad)| {
-                quad.rotate_to(quadrant::ORIENTATIONS[i]);
-                quad
-            })
-            .collect::<Vec<quadrant::BoardQuadrant>>();
-
-        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
-        (pos, Game::from_quadrants(&quadrants))
-    }
-
     // Test robot already on target
     #[test]
     fn on_target() {
@@ -264,14 +300,7 @@ mod tests {
     // Test short path
     #[test]
     fn solve() {
-        let (pos, game) = create_board();
-        let target = Target::Red(Symbol::Triangle);
-
-        let round = Round::new(
-            game.board().clone(),
-            target,
-            game.get_target_position(&target).unwrap(),
-        );
+        let (pos, round) = create_round(Target::Red(Symbol::Triangle));
 
         let expected = Path::new(
             pos.clone(),
@@ -293,13 +322,7 @@ mod tests {
     fn monte_carlo_solve() {
         let mut rng = rand::rngs::StdRng::seed_from_u64(10);
 
-        let (pos, game) = create_board();
-        let target = Target::Red(Symbol::Triangle);
-        let round = Round::new(
-            game.board().clone(),
-            target,
-            game.get_target_position(&target).unwrap(),
-        );
+        let (pos, round) = create_round(Target::Red(Symbol::Triangle));
 
         let mut tries = 0;
         let mut total_moves: u64 = 0;
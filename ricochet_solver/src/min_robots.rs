@@ -0,0 +1,233 @@
+use ricochet_board::{Robot, RobotPositions, Round, ROBOTS};
+use std::convert::TryFrom;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNodes};
+use crate::{is_cancelled, start_is_solution, Path, SolveError, SolveStats, Solver};
+
+/// Finds a solution moving as few distinct robots as possible.
+///
+/// The other solvers minimize the number of moves and only use the number of robots to break
+/// ties. This solver minimizes the number of robots first, so its solutions may need more moves
+/// than an optimal one. To do so, the robots are split into subsets in order of their size. For
+/// each subset a breadth first search is started, which only moves the robots in the subset while
+/// the other ones stay in place. The first subset size with a solution determines the number of
+/// robots, of all subsets with this size the shortest solution is returned.
+///
+/// Searching a subset without a solution visits all positions its robots can reach, which gets
+/// expensive for three or more robots.
+#[derive(Debug, Clone)]
+pub struct MinRobots {
+    /// Manages knowledge of visited nodes in the current subset.
+    visited_nodes: VisitedNodes<BasicVisitedNode>,
+    /// Statistics of the last search.
+    stats: SolveStats,
+}
+
+impl Solver for MinRobots {
+    fn solve_cancellable(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Path, SolveError> {
+        let started = Instant::now();
+        let result = self.find_path(round, start_positions, cancel.as_deref());
+        self.stats.elapsed = started.elapsed();
+        result
+    }
+
+    fn last_stats(&self) -> SolveStats {
+        self.stats
+    }
+
+    fn name(&self) -> &'static str {
+        "min-robots"
+    }
+
+    fn reset(&mut self) {
+        self.visited_nodes.clear();
+        self.stats = Default::default();
+    }
+}
+
+impl MinRobots {
+    /// Creates a new solver which finds the solution with the fewest robots moved.
+    pub fn new() -> Self {
+        Self {
+            visited_nodes: VisitedNodes::with_capacity(65536),
+            stats: Default::default(),
+        }
+    }
+
+    /// Finds the shortest solution only moving `robots` with a breadth first search.
+    ///
+    /// Returns `None` if the target can't be reached with these robots.
+    pub(crate) fn search(
+        &mut self,
+        round: &Round,
+        start_pos: &RobotPositions,
+        robots: &[Robot],
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Option<Path>, SolveError> {
+        self.visited_nodes.clear();
+        let mut current_move_positions = vec![start_pos.clone()];
+        let mut next_move_positions = Vec::new();
+
+        for move_n in 0.. {
+            if current_move_positions.is_empty() {
+                return Ok(None);
+            }
+            for pos in &current_move_positions {
+                if is_cancelled(cancel) {
+                    return Err(SolveError::Cancelled);
+                }
+                self.stats.expand(move_n);
+
+                for (new_pos, movement) in round
+                    .reachable_positions(pos)
+                    .filter(|(_, (robot, _))| robots.contains(robot))
+                {
+                    if new_pos == *start_pos
+                        || self
+                            .visited_nodes
+                            .add_node(
+                                new_pos.clone(),
+                                pos,
+                                move_n + 1,
+                                movement,
+                                &BasicVisitedNode::new,
+                            )
+                            .was_discarded()
+                    {
+                        continue;
+                    }
+                    if round.target_reached(&new_pos) {
+                        return Ok(Some(self.visited_nodes.path_to(&new_pos)));
+                    }
+                    next_move_positions.push(new_pos);
+                }
+            }
+            current_move_positions.clear();
+            std::mem::swap(&mut current_move_positions, &mut next_move_positions);
+        }
+
+        unreachable!()
+    }
+
+    /// Searches for a path like [`solve_cancellable`](Solver::solve_cancellable) without measuring
+    /// the elapsed time.
+    fn find_path(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Path, SolveError> {
+        self.stats = Default::default();
+
+        if let Some(path) = start_is_solution(round, &start_positions) {
+            return Ok(path);
+        }
+
+        // Searching every subset of an unsolvable round would visit all reachable positions.
+        let move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if move_board.is_impossible_bound(round.lower_bound(&move_board, &start_positions)) {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        // The robot of a colored target has to be part of every subset, unless a custom condition
+        // decides when the round is solved.
+        let required = Robot::try_from(round.target())
+            .ok()
+            .filter(|_| round.condition().is_none());
+        for size in 1..=ROBOTS.len() {
+            let mut best: Option<Path> = None;
+            for robots in robot_subsets(size) {
+                if matches!(required, Some(robot) if !robots.contains(&robot)) {
+                    continue;
+                }
+                let path = self.search(round, &start_positions, &robots, cancel)?;
+                best = match (best, path) {
+                    (Some(best), Some(path)) if path.len() < best.len() => Some(path),
+                    (None, path) => path,
+                    (best, _) => best,
+                };
+            }
+            if let Some(path) = best {
+                return Ok(path);
+            }
+        }
+
+        panic!("It's not possible to reach the target starting from this robot configuration")
+    }
+}
+
+impl Default for MinRobots {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns all subsets of [`ROBOTS`](ROBOTS) containing `size` robots.
+fn robot_subsets(size: usize) -> impl Iterator<Item = Vec<Robot>> {
+    (0u8..1 << ROBOTS.len())
+        .filter(move |mask| mask.count_ones() as usize == size)
+        .map(|mask| {
+            ROBOTS
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & 1 << i != 0)
+                .map(|(_, &robot)| robot)
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{round, Robot, Symbol, Target};
+
+    use super::{robot_subsets, MinRobots};
+    use crate::tests::create_round;
+    use crate::{AStar, Solver};
+
+    #[test]
+    fn subsets() {
+        assert_eq!(robot_subsets(1).count(), 4);
+        assert_eq!(robot_subsets(2).count(), 6);
+        assert_eq!(
+            robot_subsets(4).collect::<Vec<_>>(),
+            vec![vec![Robot::Red, Robot::Blue, Robot::Green, Robot::Yellow]]
+        );
+    }
+
+    #[test]
+    fn fewer_robots_than_optimal() {
+        let (pos, round) = create_round(Target::Red(Symbol::Square));
+
+        let optimal = AStar::new().solve(&round, pos.clone());
+        let path = MinRobots::new().solve(&round, pos);
+        assert!(path.verify(&round));
+        assert_eq!((optimal.len(), optimal.robot_count()), (7, 2));
+        assert_eq!((path.len(), path.robot_count()), (8, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "It's not possible to reach the target")]
+    fn impossible_round() {
+        let (round, pos) = round!(
+            Target::Red(Symbol::Square),
+            "
+            R . . . .
+                -
+            . .|*|. .
+                -
+            . . . . .
+            . . . . .
+            B G . . Y
+            "
+        );
+        MinRobots::new().solve(&round, pos);
+    }
+}
@@ -1,9 +1,12 @@
 use fxhash::FxHashMap;
 use ricochet_board::{
-    Board, Direction, Position, PositionEncoding, Robot, RobotPositions, Target, DIRECTIONS, ROBOTS,
+    Board, Direction, Game, Position, PositionEncoding, Robot, RobotPositions, Target, DIRECTIONS,
+    ROBOTS,
 };
 use std::collections::hash_map::Entry;
-use std::convert::TryInto;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::{TryFrom, TryInto};
+use std::fmt::Write;
 use std::ops;
 
 use crate::Path;
@@ -35,23 +38,38 @@ impl AddNodeOutcome {
     }
 }
 
+/// The hasher used by [`VisitedNodes`](VisitedNodes), which can be changed with the `fnv` and
+/// `ahash` features to compare their performance.
+#[cfg(feature = "ahash")]
+type VisitedHasher = ahash::RandomState;
+#[cfg(all(feature = "fnv", not(feature = "ahash")))]
+type VisitedHasher = fnv::FnvBuildHasher;
+#[cfg(not(any(feature = "fnv", feature = "ahash")))]
+type VisitedHasher = fxhash::FxBuildHasher;
+
 /// Stores `RobotPositions` and information regarding the positions like nodes in a tree.
 ///
 /// This just wraps a map from `RobotPositions` to a `VisitedNode` and provides convenience methods
 /// like [`add_node`](VisitedNodes::add_node) or [`path_to`](VisitedNodes::path_to).
 #[derive(Debug, Clone)]
 pub(crate) struct VisitedNodes<N: VisitedNode> {
-    nodes: FxHashMap<RobotPositions, N>,
+    nodes: HashMap<RobotPositions, N, VisitedHasher>,
 }
 
 impl<N: VisitedNode> VisitedNodes<N> {
     /// Creates a new `VisitedNodes` with the given `capacity`.
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            nodes: FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
+            nodes: HashMap::with_capacity_and_hasher(capacity, Default::default()),
         }
     }
 
+    /// Returns the number of stored nodes.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
     /// Removes all stored nodes.
     pub fn clear(&mut self) {
         self.nodes.clear()
@@ -188,6 +206,10 @@ impl VisitedNode for BasicVisitedNode {
 /// positioned perfectly. If the lower bound of a position is the square of the side_length of the
 /// board or the number of fields plus one, then the target is impossible to reach from that field.
 ///
+/// The bounds only depend on the walls of the board, nothing is assumed about its layout. Fields
+/// in the walled-off center of a standard board are unreachable because of their walls, while the
+/// center of a board without the center block is treated like any other field.
+///
 /// `LeastMovesBoard` implements `Index<Position>` which makes getting the calculated minimum of a
 /// positon easy.
 #[derive(Debug, Clone, Default)]
@@ -206,18 +228,38 @@ impl LeastMovesBoard {
     /// of the positions from which the target can never be reached are left. Those positions are
     /// marked with a lower bound of `board.side_length().pow(2)`, a bound longer than possible on a
     /// square board.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(board)))]
     pub fn new(board: &Board, target_position: Position) -> Self {
+        Self::with_buffers(
+            board,
+            target_position,
+            &mut Vec::with_capacity(256),
+            &mut Vec::with_capacity(256),
+        )
+    }
+
+    /// Creates a new board like [`new`](Self::new), using `current_moves` and `next_moves` to
+    /// store the positions to expand.
+    ///
+    /// Both buffers are cleared before and left empty after use, so they can be reused for the next
+    /// board.
+    fn with_buffers(
+        board: &Board,
+        target_position: Position,
+        current_moves: &mut Vec<Position>,
+        next_moves: &mut Vec<Position>,
+    ) -> Self {
         let len = board.side_length() as usize;
         let mut move_board = vec![vec![len * len; len]; len];
 
-        let mut current_moves = Vec::with_capacity(256);
-        let mut next_moves = current_moves.clone();
+        current_moves.clear();
+        next_moves.clear();
 
         move_board[target_position.column() as usize][target_position.row() as usize] = 0;
         current_moves.push(target_position);
 
         for move_n in 1usize.. {
-            for &pos in &current_moves {
+            for &pos in current_moves.iter() {
                 for &dir in DIRECTIONS.iter() {
                     // Start from pos for each direction.
                     let mut check_pos = pos;
@@ -237,11 +279,11 @@ impl LeastMovesBoard {
                 }
             }
 
+            current_moves.clear();
             if next_moves.is_empty() {
                 break;
             }
-            current_moves.clear();
-            std::mem::swap(&mut current_moves, &mut next_moves);
+            std::mem::swap(current_moves, next_moves);
         }
 
         Self {
@@ -268,11 +310,80 @@ impl LeastMovesBoard {
         }
     }
 
+    /// Returns the position of the target the board has been calculated for.
+    pub fn target_position(&self) -> Position {
+        self.target_position
+    }
+
     /// Checks whether the `target` is impossible to reach by checking if the lower bound returned
     /// by [`min_moves`](Self::min_moves) is greater than or equal to the number of fields on the
     /// board.
     pub fn is_unsolvable(&self, robots: &RobotPositions, target: Target) -> bool {
-        self.min_moves(robots, target) >= self.board.len().pow(2)
+        self.is_impossible_bound(self.min_moves(robots, target))
+    }
+
+    /// Renders the board as an SVG image with each field colored by its lower bound.
+    ///
+    /// The colors go from light yellow on the target to red on the fields needing the most moves,
+    /// fields from which the target can't be reached are dark gray. Each field also shows its
+    /// lower bound as text.
+    pub fn to_svg(&self) -> String {
+        const FIELD_SIZE: usize = 24;
+        let len = self.board.len();
+        let unreachable = len.pow(2);
+        let max = self
+            .board
+            .iter()
+            .flatten()
+            .filter(|&&moves| moves < unreachable)
+            .max()
+            .map_or(1, |&max| max.max(1));
+
+        let size = len * FIELD_SIZE;
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+            size
+        );
+        svg.push('\n');
+        for (column, fields) in self.board.iter().enumerate() {
+            for (row, &moves) in fields.iter().enumerate() {
+                let (x, y) = (column * FIELD_SIZE, row * FIELD_SIZE);
+                let color = if moves >= unreachable {
+                    "#404040".to_string()
+                } else {
+                    let fade = 200 * moves / max;
+                    format!("#ff{:02x}{:02x}", 255 - fade, 200 - fade)
+                };
+                writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{color}" stroke="black"/>"#,
+                    x = x,
+                    y = y,
+                    size = FIELD_SIZE,
+                    color = color
+                )
+                .unwrap();
+                if moves < unreachable {
+                    writeln!(
+                        svg,
+                        r#"<text x="{}" y="{}" font-size="12" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                        x + FIELD_SIZE / 2,
+                        y + FIELD_SIZE / 2,
+                        moves
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Checks whether a `lower_bound` calculated with this board, e.g. by
+    /// [`Round::lower_bound`](ricochet_board::Round::lower_bound), shows that the target is
+    /// impossible to reach.
+    pub fn is_impossible_bound(&self, lower_bound: usize) -> bool {
+        lower_bound >= self.board.len().pow(2)
     }
 }
 
@@ -284,11 +395,108 @@ impl ops::Index<Position> for LeastMovesBoard {
     }
 }
 
+/// Contains a [`LeastMovesBoard`](LeastMovesBoard) for each of several targets on the same board.
+///
+/// Useful to estimate the difficulty of every target on a board, since the buffers used while
+/// calculating the boards are shared.
+#[derive(Debug, Clone, Default)]
+pub struct AllTargetsMoveBoard {
+    boards: BTreeMap<Target, LeastMovesBoard>,
+}
+
+impl AllTargetsMoveBoard {
+    /// Creates a [`LeastMovesBoard`](LeastMovesBoard) for each of the `targets` and their
+    /// positions.
+    pub fn new(board: &Board, targets: &[(Target, Position)]) -> Self {
+        let mut current_moves = Vec::with_capacity(256);
+        let mut next_moves = Vec::with_capacity(256);
+        let boards = targets
+            .iter()
+            .map(|&(target, position)| {
+                let move_board = LeastMovesBoard::with_buffers(
+                    board,
+                    position,
+                    &mut current_moves,
+                    &mut next_moves,
+                );
+                (target, move_board)
+            })
+            .collect();
+        Self { boards }
+    }
+
+    /// Returns the move board of `target` or `None` if it wasn't part of the targets used to
+    /// create `self`.
+    pub fn get(&self, target: Target) -> Option<&LeastMovesBoard> {
+        self.boards.get(&target)
+    }
+
+    /// Returns the lower bound of the number of moves needed to reach the `target` with `robots`.
+    ///
+    /// See [`LeastMovesBoard::min_moves`](LeastMovesBoard::min_moves) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` wasn't part of the targets used to create `self`.
+    pub fn min_moves(&self, robots: &RobotPositions, target: Target) -> usize {
+        self.get(target)
+            .expect("No move board has been calculated for the target")
+            .min_moves(robots, target)
+    }
+}
+
+/// Finds the target of `game` with the smallest lower bound of moves needed to reach it with a
+/// robot on `robot_pos`.
+///
+/// The lower bounds are taken from a [`LeastMovesBoard`](LeastMovesBoard) of each target, so the
+/// other robots are ignored. If `color` is given, only the targets of this color and the spiral
+/// are considered, since the robot can't reach the other ones. Ties are broken by the order of
+/// the targets. Returns `None` if none of the considered targets can be reached from `robot_pos`.
+pub fn nearest_target(
+    game: &Game,
+    robot_pos: Position,
+    color: Option<Robot>,
+) -> Option<(Target, usize)> {
+    let targets = game
+        .iter_targets()
+        .filter(|&(target, _)| match (color, Robot::try_from(target)) {
+            (Some(color), Ok(robot)) => robot == color,
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+    AllTargetsMoveBoard::new(game.board(), &targets)
+        .boards
+        .iter()
+        .map(|(&target, move_board)| (target, move_board, move_board[robot_pos]))
+        .filter(|(_, move_board, moves)| !move_board.is_impossible_bound(*moves))
+        .map(|(target, _, moves)| (target, moves))
+        .min_by_key(|&(_, moves)| moves)
+}
+
+/// Finds the first repeated positions in `positions_history`.
+///
+/// Returns the indices `(first, repeated)` of the first positions that occur a second time, where
+/// `repeated` is as small as possible. Once a deterministic policy repeats positions it oscillates
+/// between the positions from `first` to `repeated` forever, e.g. a robot moving back and forth
+/// between two fields leads to `repeated == first + 2`. Returns `None` if all positions differ.
+pub fn detect_cycle(positions_history: &[RobotPositions]) -> Option<(usize, usize)> {
+    let mut seen = FxHashMap::default();
+    for (i, positions) in positions_history.iter().enumerate() {
+        if let Some(&first) = seen.get(positions) {
+            return Some((first, i));
+        }
+        seen.insert(positions, i);
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
-    use ricochet_board::{Board, Position, PositionEncoding, RobotPositions, Target};
+    use ricochet_board::{
+        quadrant, Board, Position, PositionEncoding, Robot, RobotPositions, Symbol, Target, ROBOTS,
+    };
 
-    use super::LeastMovesBoard;
+    use super::{detect_cycle, nearest_target, AllTargetsMoveBoard, LeastMovesBoard};
 
     #[test]
     fn empty_move_board() {
@@ -300,6 +508,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn open_move_board() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let target = Position::new(7, 7);
+        let move_board = LeastMovesBoard::new(&board, target);
+
+        // Every field on the row or column of the target reaches it in one move, every other
+        // field in two, including the center fields walled off on a standard board.
+        for column in 0..16 {
+            for row in 0..16 {
+                let expected = match (column == 7, row == 7) {
+                    (true, true) => 0,
+                    (true, false) | (false, true) => 1,
+                    (false, false) => 2,
+                };
+                assert_eq!(move_board[Position::new(column, row)], expected);
+            }
+        }
+
+        let standard = quadrant::game_from_seed(0);
+        let move_board = LeastMovesBoard::new(standard.board(), Position::new(0, 0));
+        assert!(move_board.is_impossible_bound(move_board[Position::new(8, 8)]));
+    }
+
     #[test]
     fn walled_move_board() {
         let board = Board::new_empty(3)
@@ -341,4 +573,80 @@ mod tests {
         assert_eq!(move_board.min_moves(&rob_pos, Target::Spiral), 4);
         assert!(move_board.is_unsolvable(&rob_pos, Target::Spiral));
     }
+
+    #[test]
+    fn svg_heatmap() {
+        let board = Board::new_empty(3)
+            .wall_enclosure()
+            .set_horizontal_line(0, 0, 1)
+            .set_horizontal_line(1, 1, 1)
+            .set_vertical_line(1, 1, 1);
+        let svg = LeastMovesBoard::new(&board, Position::new(0, 0)).to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 9);
+        assert_eq!(svg.matches("<text").count(), 9);
+        // The target and the fields needing the most moves.
+        assert_eq!(svg.matches("#ffffc8").count(), 1);
+        assert_eq!(svg.matches("#ff3700").count(), 3);
+
+        let game = quadrant::game_from_seed(0);
+        let svg = LeastMovesBoard::new(game.board(), Position::new(0, 0)).to_svg();
+        // The center fields can't be reached.
+        assert_eq!(svg.matches("#404040").count(), 4);
+    }
+
+    #[test]
+    fn all_targets_move_board() {
+        let game = quadrant::game_from_seed(0);
+        let targets = game.iter_targets().collect::<Vec<_>>();
+        let all_boards = AllTargetsMoveBoard::new(game.board(), &targets);
+        let rob_pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+
+        for (target, position) in targets {
+            let move_board = LeastMovesBoard::new(game.board(), position);
+            assert_eq!(all_boards.get(target).unwrap().board, move_board.board);
+            assert_eq!(
+                all_boards.min_moves(&rob_pos, target),
+                move_board.min_moves(&rob_pos, target)
+            );
+        }
+    }
+
+    #[test]
+    fn nearest_targets() {
+        let game = quadrant::game_from_seed(0);
+        let target = Target::Blue(Symbol::Hexagon);
+        let position = game.get_target_position(&target).unwrap();
+        assert_eq!(
+            nearest_target(&game, position, Some(Robot::Blue)),
+            Some((target, 0))
+        );
+        assert_eq!(nearest_target(&game, position, None), Some((target, 0)));
+
+        let (nearest, moves) = nearest_target(&game, position, Some(Robot::Red)).unwrap();
+        assert!(matches!(nearest, Target::Red(_) | Target::Spiral));
+        assert!(moves > 0);
+        let pos = Position::new(0, 0);
+        let (_, any_moves) = nearest_target(&game, pos, None).unwrap();
+        for &robot in ROBOTS.iter() {
+            let (_, moves) = nearest_target(&game, pos, Some(robot)).unwrap();
+            assert!(moves >= any_moves);
+        }
+    }
+
+    #[test]
+    fn cycle() {
+        let a = RobotPositions::from_tuples(&[(0, 0), (1, 0), (2, 0), (3, 0)]);
+        let b = RobotPositions::from_tuples(&[(0, 5), (1, 0), (2, 0), (3, 0)]);
+        let c = RobotPositions::from_tuples(&[(0, 5), (1, 5), (2, 0), (3, 0)]);
+
+        assert_eq!(detect_cycle(&[]), None);
+        assert_eq!(detect_cycle(&[a.clone(), b.clone(), c.clone()]), None);
+        assert_eq!(
+            detect_cycle(&[a.clone(), b.clone(), c.clone(), b.clone(), c.clone(), a]),
+            Some((1, 3))
+        );
+        assert_eq!(detect_cycle(&[c, b.clone(), b]), Some((1, 2)));
+    }
 }
@@ -0,0 +1,78 @@
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use ricochet_board::{quadrant, Position, RobotPositions, Round};
+use ricochet_solver::{solver_by_name, AStar, Solver};
+
+/// The solvers which are guaranteed to find an optimal solution.
+const OPTIMAL_SOLVERS: [&str; 3] = ["breadth-first", "a-star", "ida-star"];
+
+/// Chooses distinct starting positions outside of the center of a standard board, which don't
+/// reach the target already.
+fn random_start(round: &Round, rng: &mut rand::rngs::StdRng) -> RobotPositions {
+    let side_length = round.board().side_length();
+    let fields: Vec<Position> = (0..side_length)
+        .flat_map(|col| (0..side_length).map(move |row| Position::new(col, row)))
+        .filter(|pos| !((7..=8).contains(&pos.column()) && (7..=8).contains(&pos.row())))
+        .collect();
+    loop {
+        let chosen: Vec<(_, _)> = fields
+            .choose_multiple(rng, 4)
+            .map(|&pos| pos.into())
+            .collect();
+        let start = RobotPositions::from_tuples(&[chosen[0], chosen[1], chosen[2], chosen[3]]);
+        if !round.target_reached(&start) {
+            return start;
+        }
+    }
+}
+
+#[test]
+fn optimal_solvers_agree() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    for seed in (0..quadrant::DISTINCT_STANDARD_ROUNDS).step_by(997) {
+        let round = quadrant::round_from_seed(seed);
+        let start = random_start(&round, &mut rng);
+
+        let mut solvers: Vec<Box<dyn Solver>> = OPTIMAL_SOLVERS
+            .iter()
+            .map(|&name| solver_by_name(name).unwrap())
+            .collect();
+        solvers.push(Box::new(AStar::with_move_table()));
+
+        let lengths: Vec<usize> = solvers
+            .iter_mut()
+            .map(|solver| {
+                let path = solver.solve(&round, start.clone());
+                assert!(
+                    path.verify(&round),
+                    "{} found an invalid path for seed {}",
+                    solver.name(),
+                    seed
+                );
+                path.len()
+            })
+            .collect();
+        assert!(
+            lengths.iter().all(|&len| len == lengths[0]),
+            "solvers disagree for seed {}: {:?}",
+            seed,
+            lengths
+        );
+    }
+}
+
+#[test]
+fn other_solvers_are_valid() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+    for seed in (0..quadrant::DISTINCT_STANDARD_ROUNDS).step_by(1999) {
+        let round = quadrant::round_from_seed(seed);
+        let start = random_start(&round, &mut rng);
+        let optimal = AStar::new().solve(&round, start.clone()).len();
+
+        for name in ricochet_solver::SOLVER_NAMES.iter() {
+            let path = solver_by_name(name).unwrap().solve(&round, start.clone());
+            assert!(path.verify(&round), "{} found an invalid path", name);
+            assert!(path.len() >= optimal);
+        }
+    }
+}
@@ -2,9 +2,10 @@ use chrono::Local;
 use itertools::Itertools;
 use rand::Rng;
 use rayon::iter::{ParallelBridge, ParallelIterator};
-use ricochet_board::{RobotPositions, Round};
-use ricochet_solver::{Path, Solver};
+use ricochet_board::{PositionEncoding, RobotPositions, Round};
+use ricochet_solver::{Path, SolveStats, Solver};
 use serde::Serialize;
+use std::convert::TryFrom;
 use std::sync::mpsc;
 use std::{fs, path, thread};
 
@@ -46,9 +47,9 @@ fn main() {
         .par_bridge()
         .for_each(|(board_seed, sender)| {
             let mut data = SolutionData::new(board_seed);
-            let start_time = Local::now();
-            let path = ricochet_solver::AStar::new().solve(&data.round(), data.start_positions());
-            data.finalize(Local::now() - start_time, path);
+            let (path, stats) = ricochet_solver::AStar::new()
+                .solve_with_stats(&data.round(), data.start_positions());
+            data.finalize(stats, path);
             sender.send(data).expect("could not send data to writer");
         });
     println!("{}: waiting for writer to finish", Local::now());
@@ -84,8 +85,8 @@ impl SolutionData {
         }
     }
 
-    pub fn finalize(&mut self, duration: chrono::Duration, path: Path) {
-        self.time_micros = duration.num_microseconds();
+    pub fn finalize(&mut self, stats: SolveStats, path: Path) {
+        self.time_micros = i64::try_from(stats.elapsed().as_micros()).ok();
         self.length = Some(path.len());
         self.robots_used = Some(path.movements().iter().map(|mm| mm.0).unique().count());
         self.path = Some(path);
@@ -99,12 +100,12 @@ impl SolutionData {
         RobotPositions::from_tuples(&Self::positions_as_tuples(self.positions))
     }
 
-    fn positions_as_tuples(pos: u32) -> [(u16, u16); 4] {
+    fn positions_as_tuples(pos: u32) -> [(PositionEncoding, PositionEncoding); 4] {
         let mut out = [(0, 0); 4];
 
         for (shift, out_idx) in (0..4).rev().enumerate() {
-            out[out_idx].1 = ((pos >> (8 * shift)) & 0b1111) as u16;
-            out[out_idx].0 = ((pos >> (8 * shift + 4)) & 0b1111) as u16;
+            out[out_idx].1 = ((pos >> (8 * shift)) & 0b1111) as PositionEncoding;
+            out[out_idx].0 = ((pos >> (8 * shift + 4)) & 0b1111) as PositionEncoding;
         }
 
         out